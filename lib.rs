@@ -2,6 +2,8 @@
 
 #[ink::contract]
 mod inky_bank {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::CallFlags;
     use ink::storage::Mapping;
     use ink::prelude::vec::Vec;
 
@@ -11,8 +13,88 @@ mod inky_bank {
         total_supply: u128,
         balances: Mapping<AccountId, u128>,
         allowances: Mapping<(AccountId, AccountId), u128>,
-        paused: bool,
+        pause_flags: u8,
         blacklist: Mapping<AccountId, bool>,
+        max_wallet_amount: u128,
+        max_batch_size: u32,
+        compact_batch_events: bool,
+        blacklisted_at: Mapping<AccountId, u64>,
+        blacklist_grace_secs: u64,
+        pause_exempt: Mapping<AccountId, bool>,
+        total_allowance_granted: Mapping<AccountId, u128>,
+        allowance_received: Mapping<AccountId, u128>,
+        whitelist_mode: bool,
+        whitelist: Mapping<AccountId, bool>,
+        transfers_enabled: bool,
+        stakes: Mapping<AccountId, u128>,
+        reward_debt: Mapping<AccountId, u128>,
+        total_staked: u128,
+        reward_per_block: u128,
+        acc_reward_per_share: u128,
+        last_reward_block: BlockNumber,
+        block_self_deposit: bool,
+        flash_mint_fee_bps: u16,
+        block_self_approval: bool,
+        holder_count: u32,
+        event_seq: u64,
+        max_allowance: u128,
+        last_owner_action: Timestamp,
+        backup_guardian: Option<AccountId>,
+        inactivity_secs: u64,
+        unit_scale: u128,
+        allowances_frozen: bool,
+        invoices: Mapping<u64, TransferRecord>,
+        allow_zero_transfers: bool,
+        mint_cooldown_secs: u64,
+        last_mint_at: Option<Timestamp>,
+        blacklist_action: BlacklistAction,
+        large_transfer_threshold: u128,
+        pending_large_transfers: Mapping<u64, TransferRecord>,
+        next_large_transfer_id: u64,
+        soulbound: bool,
+        prune_zero_balances: bool,
+        sub_balances: Mapping<(u32, AccountId), u128>,
+        sub_supplies: Mapping<u32, u128>,
+        exemption_flags: Mapping<AccountId, u8>,
+        bridge_nonce: u64,
+        bridge_relayer: Option<AccountId>,
+        processed_bridge_mints: Mapping<(u32, u64), bool>,
+        max_growth_bps_per_period: u16,
+        growth_period_secs: u64,
+        growth_period_start: Timestamp,
+        growth_period_base_supply: u128,
+        minted_this_period: u128,
+        min_balance: u128,
+        dust_sweep: bool,
+        record_recent_transfers: bool,
+        recent_transfers: Mapping<u32, RecentTransfer>,
+        recent_transfers_next: u32,
+        recent_transfers_len: u32,
+        swaps: Mapping<u64, Swap>,
+        next_swap_id: u64,
+        min_transfer_amount: u128,
+        min_reserve_ratio_bps: u16,
+        reserve_price: Balance,
+        compliance_hook: Option<AccountId>,
+        created_at_block: BlockNumber,
+        created_at_time: Timestamp,
+        spender_daily_limit: u128,
+        spender_daily_spent: Mapping<AccountId, (Timestamp, u128)>,
+        total_escrowed: u128,
+        subscriptions: Mapping<(AccountId, AccountId), Subscription>,
+        self_confirm_thresholds: Mapping<AccountId, u128>,
+        self_confirm_delays: Mapping<AccountId, u64>,
+        pending_self_transfers: Mapping<u64, (TransferRecord, Timestamp)>,
+        next_self_transfer_id: u64,
+        hold_secs: u64,
+        hold_tranches: Mapping<AccountId, Vec<HoldTranche>>,
+        mint_allowlist_enabled: bool,
+        mint_allowlist: Mapping<AccountId, bool>,
+        pending_self_confirm_thresholds: Mapping<AccountId, (u128, Timestamp)>,
+        pending_self_confirm_delays: Mapping<AccountId, (u64, Timestamp)>,
+        infinite_allowances_granted: Mapping<AccountId, u32>,
+        infinite_allowances_received: Mapping<AccountId, u32>,
+        deferred_rewards: Mapping<AccountId, u128>,
     }
 
     /// Events
@@ -21,6 +103,7 @@ mod inky_bank {
         #[ink(topic)]
         to: AccountId,
         amount: u128,
+        seq: u64,
     }
 
     #[ink(event)]
@@ -30,6 +113,96 @@ mod inky_bank {
         #[ink(topic)]
         to: AccountId,
         amount: u128,
+        seq: u64,
+    }
+
+    #[ink(event)]
+    pub struct BatchTransfer {
+        #[ink(topic)]
+        from: AccountId,
+        count: u32,
+        total: u128,
+    }
+
+    #[ink(event)]
+    pub struct LargeTransferQueued {
+        #[ink(topic)]
+        id: u64,
+        #[ink(topic)]
+        from: AccountId,
+        to: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct LargeTransferRejected {
+        #[ink(topic)]
+        id: u64,
+    }
+
+    #[ink(event)]
+    pub struct SwapCreated {
+        #[ink(topic)]
+        id: u64,
+        #[ink(topic)]
+        seller: AccountId,
+        #[ink(topic)]
+        counterparty: AccountId,
+        token_amount: u128,
+        native_price: Balance,
+        expiry: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct SwapAccepted {
+        #[ink(topic)]
+        id: u64,
+    }
+
+    #[ink(event)]
+    pub struct SwapCancelled {
+        #[ink(topic)]
+        id: u64,
+    }
+
+    #[ink(event)]
+    pub struct SubMinted {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        to: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct SubTransfer {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct BridgeBurn {
+        #[ink(topic)]
+        from: AccountId,
+        amount: u128,
+        #[ink(topic)]
+        dest_chain: u32,
+        dest_address: [u8; 32],
+        nonce: u64,
+    }
+
+    /// A best-effort cross-contract hook returned an error; the calling message
+    /// still completed.
+    #[ink(event)]
+    pub struct HookFailed {
+        #[ink(topic)]
+        hook: AccountId,
+        selector: [u8; 4],
     }
 
     #[ink(event)]
@@ -41,17 +214,52 @@ mod inky_bank {
         amount: u128,
     }
 
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
     #[ink(event)]
     pub struct Paused {
         #[ink(topic)]
         paused: bool,
     }
 
+    #[ink(event)]
+    pub struct PauseFlagsSet {
+        flags: u8,
+    }
+
     #[ink(event)]
     pub struct Burned {
         #[ink(topic)]
         from: AccountId,
         amount: u128,
+        seq: u64,
+    }
+
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct RewardClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        amount: u128,
     }
 
     #[ink(event)]
@@ -72,11 +280,238 @@ mod inky_bank {
         AccountBlacklisted,
         InsufficientAllowance,
         InvalidBatchOperation,
+        ExceedsMaxWallet,
+        NotWhitelisted,
+        TransferRejected,
+        TransfersDisabled,
+        InsufficientStake,
+        SelfDepositBlocked,
+        AllowanceChanged,
+        FlashMintNotRepaid,
+        InvalidMigrationTarget,
+        SelfApproval,
+        AllowanceTooHigh,
+        AllowancesFrozen,
+        DuplicateInvoice,
+        TransferFailed,
+        MintCooldownActive,
+        SeizureDisabled,
+        NotBlacklisted,
+        Overflow,
+        SupplyInvariantBroken,
+        NotGuardian,
+        OwnerStillActive,
+        LargeTransferRequiresApproval,
+        UnknownLargeTransfer,
+        Soulbound,
+        OwnershipRenounced,
+        NotRelayer,
+        AlreadyProcessed,
+        GrowthCapExceeded,
+        BelowMinBalance,
+        UnknownSwap,
+        NotSwapParty,
+        SwapExpired,
+        SwapNotExpired,
+        IncorrectPayment,
+        BelowMinimum,
+        InsufficientReserves,
+        DailyLimitExceeded,
+        NoSubscription,
+        SubscriptionNotDue,
+        SelfConfirmRequired,
+        UnknownSelfTransfer,
+        SelfTransferNotReady,
+        HoldPeriodActive,
+        ZeroAddress,
+        CannotBlacklistProtected,
+        ZeroPeriod,
+        NotLargeTransfer,
+        InactivityNotSet,
     }
 
     /// Result type for our contract functions
     pub type Result<T> = core::result::Result<T, Error>;
 
+    /// `(from, to, amount)` of a recorded transfer
+    pub type TransferRecord = (AccountId, AccountId, u128);
+
+    /// `(from, to, amount, timestamp)` of a transfer in the recent-transfers ring
+    pub type RecentTransfer = (AccountId, AccountId, u128, Timestamp);
+
+    /// `(amount, until)` of one hold-period lock on received tokens
+    pub type HoldTranche = (u128, Timestamp);
+
+    /// What the owner may do with a blacklisted account's funds
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum BlacklistAction {
+        /// Funds stay frozen in place.
+        Block,
+        /// The owner may move the whole balance out with `seize`.
+        Seize,
+    }
+
+    /// Snapshot of the owner-configurable limits and switches
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TokenConfig {
+        pub pause_flags: u8,
+        pub transfers_enabled: bool,
+        pub whitelist_mode: bool,
+        pub block_self_deposit: bool,
+        pub compact_batch_events: bool,
+        pub max_wallet_amount: u128,
+        pub max_batch_size: u32,
+        pub blacklist_grace_secs: u64,
+        pub reward_per_block: u128,
+        pub flash_mint_fee_bps: u16,
+        pub block_self_approval: bool,
+        pub max_allowance: u128,
+        pub unit_scale: u128,
+        pub allowances_frozen: bool,
+        pub allow_zero_transfers: bool,
+        pub mint_cooldown_secs: u64,
+        pub blacklist_action: BlacklistAction,
+        pub large_transfer_threshold: u128,
+        pub soulbound: bool,
+        pub prune_zero_balances: bool,
+        pub max_growth_bps_per_period: u16,
+        pub growth_period_secs: u64,
+        pub min_balance: u128,
+        pub dust_sweep: bool,
+        pub record_recent_transfers: bool,
+        pub min_transfer_amount: u128,
+        pub min_reserve_ratio_bps: u16,
+        pub reserve_price: Balance,
+        pub spender_daily_limit: u128,
+        pub hold_secs: u64,
+        pub mint_allowlist_enabled: bool,
+    }
+
+    /// OTC offer of escrowed tokens to one counterparty for a fixed native price
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Swap {
+        pub seller: AccountId,
+        pub counterparty: AccountId,
+        pub token_amount: u128,
+        pub native_price: Balance,
+        /// Block timestamp (ms) after which the swap can no longer be accepted.
+        pub expiry: Timestamp,
+    }
+
+    /// Recurring pull a holder has granted a spender
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Subscription {
+        pub amount_per_period: u128,
+        pub period_secs: u64,
+        /// Timestamp (ms) of the last pull; `None` until the first one.
+        pub last_pull_at: Option<Timestamp>,
+    }
+
+    /// Bits of `pause_flags`, one per pausable group of operations
+    pub struct PauseFlags;
+
+    impl PauseFlags {
+        pub const TRANSFERS: u8 = 1 << 0;
+        pub const MINTS: u8 = 1 << 1;
+        pub const BURNS: u8 = 1 << 2;
+        pub const APPROVALS: u8 = 1 << 3;
+        pub const ALL: u8 = Self::TRANSFERS | Self::MINTS | Self::BURNS | Self::APPROVALS;
+    }
+
+    /// Bits of `exemption_flags`, one per per-account waivable transfer restriction
+    pub struct ExemptionFlags;
+
+    impl ExemptionFlags {
+        pub const MAX_WALLET: u8 = 1 << 0;
+        pub const LARGE_TRANSFER: u8 = 1 << 1;
+        pub const DAILY_LIMIT: u8 = 1 << 2;
+        pub const HOLD_PERIOD: u8 = 1 << 3;
+    }
+
+    /// Bits returned by `supported_features`, one per optional capability that is
+    /// switched on for this deployment
+    pub struct FeatureFlags;
+
+    impl FeatureFlags {
+        pub const STAKING_REWARDS: u64 = 1 << 0;
+        pub const BRIDGING: u64 = 1 << 1;
+        pub const WHITELIST: u64 = 1 << 2;
+        pub const SOULBOUND: u64 = 1 << 3;
+        pub const MAX_WALLET: u64 = 1 << 4;
+        pub const LARGE_TRANSFER_QUEUE: u64 = 1 << 5;
+        pub const GROWTH_CAP: u64 = 1 << 6;
+        pub const MIN_BALANCE: u64 = 1 << 7;
+        pub const MINT_COOLDOWN: u64 = 1 << 8;
+        pub const BLACKLIST_SEIZURE: u64 = 1 << 9;
+        pub const FLASH_MINT_FEE: u64 = 1 << 10;
+        pub const RECENT_TRANSFERS: u64 = 1 << 11;
+        pub const RESERVE_RATIO: u64 = 1 << 12;
+        pub const COMPLIANCE_HOOK: u64 = 1 << 13;
+        pub const SPENDER_DAILY_LIMIT: u64 = 1 << 14;
+        pub const HOLD_PERIOD: u64 = 1 << 15;
+        pub const MINT_ALLOWLIST: u64 = 1 << 16;
+    }
+
+    /// Length of the `spender_daily_limit` window, in milliseconds.
+    const DAY_MS: Timestamp = 24 * 60 * 60 * 1000;
+
+    /// Slots in the `recent_transfers` ring buffer.
+    const RECENT_TRANSFERS_CAPACITY: u32 = 16;
+
+    /// Open hold-period locks kept per account before receipts start sharing one.
+    const MAX_HOLD_TRANCHES: usize = 8;
+
+    /// Fixed-point scale for `acc_reward_per_share`.
+    const ACC_REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+    /// `a * b / denominator` rounded down, through a 256-bit product so large
+    /// operands can't overflow. `None` on a zero denominator or a quotient that
+    /// doesn't fit in `u128`.
+    fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let mask = u128::from(u64::MAX);
+        let (a_hi, a_lo) = (a >> 64, a & mask);
+        let (b_hi, b_lo) = (b >> 64, b & mask);
+        let lo_lo = a_lo * b_lo;
+        let hi_lo = a_hi * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let cross = (lo_lo >> 64) + (hi_lo & mask) + (lo_hi & mask);
+        let lo = (cross << 64) | (lo_lo & mask);
+        let hi = a_hi * b_hi + (hi_lo >> 64) + (lo_hi >> 64) + (cross >> 64);
+        if hi >= denominator {
+            return None;
+        }
+
+        let mut remainder = hi;
+        let mut quotient = 0u128;
+        for bit in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((lo >> bit) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= denominator {
+                remainder = remainder.wrapping_sub(denominator);
+                quotient |= 1;
+            }
+        }
+        Some(quotient)
+    }
+
     impl Default for InkyBank {
         fn default() -> Self {
             Self::new()
@@ -87,204 +522,5235 @@ mod inky_bank {
         /// Constructor
         #[ink(constructor)]
         pub fn new() -> Self {
+            Self::with_soulbound(false)
+        }
+
+        /// Constructor for a non-transferable token: only the owner can move supply,
+        /// via `mint` and holder `burn`.
+        #[ink(constructor)]
+        pub fn with_soulbound(soulbound: bool) -> Self {
             let caller = Self::env().caller();
             Self {
                 owner: caller,
                 total_supply: 0,
                 balances: Mapping::default(),
                 allowances: Mapping::default(),
-                paused: false,
+                pause_flags: 0,
                 blacklist: Mapping::default(),
+                max_wallet_amount: u128::MAX,
+                max_batch_size: 100,
+                compact_batch_events: false,
+                blacklisted_at: Mapping::default(),
+                blacklist_grace_secs: 0,
+                pause_exempt: Mapping::default(),
+                total_allowance_granted: Mapping::default(),
+                allowance_received: Mapping::default(),
+                whitelist_mode: false,
+                whitelist: Mapping::default(),
+                transfers_enabled: true,
+                stakes: Mapping::default(),
+                reward_debt: Mapping::default(),
+                total_staked: 0,
+                reward_per_block: 0,
+                acc_reward_per_share: 0,
+                last_reward_block: Self::env().block_number(),
+                block_self_deposit: false,
+                flash_mint_fee_bps: 0,
+                block_self_approval: false,
+                holder_count: 0,
+                event_seq: 0,
+                max_allowance: u128::MAX,
+                last_owner_action: Self::env().block_timestamp(),
+                backup_guardian: None,
+                inactivity_secs: 0,
+                unit_scale: 1,
+                allowances_frozen: false,
+                invoices: Mapping::default(),
+                allow_zero_transfers: false,
+                mint_cooldown_secs: 0,
+                last_mint_at: None,
+                blacklist_action: BlacklistAction::Block,
+                large_transfer_threshold: u128::MAX,
+                pending_large_transfers: Mapping::default(),
+                next_large_transfer_id: 0,
+                soulbound,
+                prune_zero_balances: true,
+                sub_balances: Mapping::default(),
+                sub_supplies: Mapping::default(),
+                exemption_flags: Mapping::default(),
+                bridge_nonce: 0,
+                bridge_relayer: None,
+                processed_bridge_mints: Mapping::default(),
+                max_growth_bps_per_period: 0,
+                growth_period_secs: 0,
+                growth_period_start: 0,
+                growth_period_base_supply: 0,
+                minted_this_period: 0,
+                min_balance: 0,
+                dust_sweep: false,
+                record_recent_transfers: false,
+                recent_transfers: Mapping::default(),
+                recent_transfers_next: 0,
+                recent_transfers_len: 0,
+                swaps: Mapping::default(),
+                next_swap_id: 0,
+                min_transfer_amount: 0,
+                min_reserve_ratio_bps: 0,
+                reserve_price: 0,
+                compliance_hook: None,
+                created_at_block: Self::env().block_number(),
+                created_at_time: Self::env().block_timestamp(),
+                spender_daily_limit: u128::MAX,
+                spender_daily_spent: Mapping::default(),
+                total_escrowed: 0,
+                subscriptions: Mapping::default(),
+                self_confirm_thresholds: Mapping::default(),
+                self_confirm_delays: Mapping::default(),
+                pending_self_transfers: Mapping::default(),
+                next_self_transfer_id: 0,
+                hold_secs: 0,
+                hold_tranches: Mapping::default(),
+                mint_allowlist_enabled: false,
+                mint_allowlist: Mapping::default(),
+                pending_self_confirm_thresholds: Mapping::default(),
+                pending_self_confirm_delays: Mapping::default(),
+                infinite_allowances_granted: Mapping::default(),
+                infinite_allowances_received: Mapping::default(),
+                deferred_rewards: Mapping::default(),
             }
         }
 
         #[ink(message)]
         pub fn mint(&mut self, to: AccountId, amount: u128) -> Result<()> {
-            let caller = self.env().caller();
-            if caller != self.owner {
-                return Err(Error::NotOwner);
-            }
+            self.ensure_owner()?;
 
-            if self.paused {
+            if self.is_paused(PauseFlags::MINTS) {
                 return Err(Error::ContractPaused);
             }
 
-            if self.blacklist.get(to).unwrap_or(false) {
-                return Err(Error::AccountBlacklisted);
-            }
-
             if amount == 0 {
                 return Err(Error::ZeroAmount);
             }
 
-            let current_balance = self.balance_of(to);
-            let new_balance = current_balance.saturating_add(amount);
-            self.balances.insert(to, &new_balance);
+            self.ensure_mint_recipient(to, amount)?;
+
+            let now = self.env().block_timestamp();
+            self.ensure_mint_cooldown_over(now)?;
+            self.ensure_reserves_cover(amount)?;
+            self.record_period_growth(now, amount)?;
+
+            let new_balance = self.balance_of(to).saturating_add(amount);
+            self.set_balance(to, new_balance);
+            self.start_hold_period(to, amount);
 
             self.total_supply = self.total_supply.saturating_add(amount);
+            self.last_mint_at = Some(now);
 
-            self.env().emit_event(Minted { to, amount });
+            self.emit_minted(to, amount);
 
             Ok(())
         }
 
        
 
+        /// Mints `amount` to `receiver`, calls its `on_flash_mint(initiator, amount, fee,
+        /// data)` hook, then burns `amount + fee` back out of its balance. The receiver
+        /// should reject initiators it doesn't trust, since the fee comes out of its own
+        /// balance. The whole call reverts with `FlashMintNotRepaid` if the hook fails or
+        /// the receiver can't cover the burn.
         #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, amount: u128) -> Result<()> {
-            let from = self.env().caller();
+        pub fn flash_mint(&mut self, receiver: AccountId, amount: u128, data: Vec<u8>) -> Result<()> {
+            let initiator = self.env().caller();
+
+            if self.is_paused(PauseFlags::MINTS) {
+                return Err(Error::ContractPaused);
+            }
 
             if amount == 0 {
                 return Err(Error::ZeroAmount);
             }
 
-            let from_balance = self.balance_of(from);
-            if from_balance < amount {
-                return Err(Error::InsufficientBalance);
+            self.ensure_mint_recipient(receiver, amount)?;
+            self.ensure_reserves_cover(amount)?;
+
+            let fee = self.flash_mint_fee(amount);
+
+            self.credit_minted(receiver, amount);
+
+            // The receiver may call back in (e.g. `transfer` to repay), so it must see
+            // the credited state, and whatever it changes must survive this frame.
+            self.flush_state();
+            let accepted = build_call::<Environment>()
+                .call(receiver)
+                .call_flags(CallFlags::ALLOW_REENTRY)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("on_flash_mint")))
+                        .push_arg(initiator)
+                        .push_arg(amount)
+                        .push_arg(fee)
+                        .push_arg(data),
+                )
+                .returns::<bool>()
+                .try_invoke();
+            self.reload_state();
+            if !matches!(accepted, Ok(Ok(true))) {
+                return Err(Error::FlashMintNotRepaid);
             }
 
-            let new_from_balance = from_balance.saturating_sub(amount);
-            self.balances.insert(from, &new_from_balance);
+            let repayment = amount.saturating_add(fee);
+            let balance = self.balance_of(receiver);
+            if balance < repayment {
+                return Err(Error::FlashMintNotRepaid);
+            }
+            self.set_balance(receiver, balance.saturating_sub(repayment));
+            self.total_supply = self.total_supply.saturating_sub(repayment);
+            self.emit_burned(receiver, repayment);
+            Ok(())
+        }
 
-            let to_balance = self.balance_of(to);
-            let new_to_balance = to_balance.saturating_add(amount);
-            self.balances.insert(to, &new_to_balance);
+        #[ink(message)]
+        pub fn flash_mint_fee(&self, amount: u128) -> u128 {
+            amount.saturating_mul(u128::from(self.flash_mint_fee_bps)) / 10_000
+        }
 
-            self.env().emit_event(Transfer { from, to, amount });
+        #[ink(message)]
+        pub fn set_flash_mint_fee_bps(&mut self, bps: u16) -> Result<()> {
+            self.ensure_owner()?;
+            self.flash_mint_fee_bps = bps;
+            Ok(())
+        }
 
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            let from = self.env().caller();
+            let amount = self.sweep_dust(from, amount);
+            self.move_tokens(from, to, amount)?;
+            self.emit_transfer(from, to, amount);
             Ok(())
         }
 
        
 
+        /// Pre-flight for wallets: returns the error `transfer` would fail with if
+        /// `from` sent `amount` to `to` right now, or `Ok(())`.
         #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, amount: u128) -> Result<()> {
-            let owner = self.env().caller();
+        pub fn can_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.check_transfer(from, to, self.sweep_dust(from, amount))
+        }
 
-            self.allowances.insert((owner, spender), &amount);
+        /// Queues a transfer above `large_transfer_threshold` for owner approval and
+        /// returns its id. No funds move until `approve_large_transfer`, but every
+        /// other `transfer` check must already pass.
+        #[ink(message)]
+        pub fn queue_large_transfer(&mut self, to: AccountId, amount: u128) -> Result<u64> {
+            let from = self.env().caller();
 
-            self.env().emit_event(Approval { owner, spender, amount });
+            if amount <= self.large_transfer_threshold {
+                return Err(Error::NotLargeTransfer);
+            }
+
+            self.check_approvable_transfer(from, to, amount)?;
+            self.ensure_min_balance_left(from, amount)?;
+
+            let id = self.next_large_transfer_id;
+            self.next_large_transfer_id = id.saturating_add(1);
+            self.pending_large_transfers.insert(id, &(from, to, amount));
+            self.env().emit_event(LargeTransferQueued { id, from, to, amount });
+            Ok(id)
+        }
+
+        /// Executes a queued large transfer, re-running the queue-time checks against
+        /// current state.
+        #[ink(message)]
+        pub fn approve_large_transfer(&mut self, id: u64) -> Result<()> {
+            self.ensure_owner()?;
+
+            let (from, to, amount) = self
+                .pending_large_transfers
+                .take(id)
+                .ok_or(Error::UnknownLargeTransfer)?;
+
+            self.check_approvable_transfer(from, to, amount)?;
+            self.ensure_min_balance_left(from, amount)?;
+
+            self.apply_transfer(from, to, amount)?;
+            self.emit_transfer(from, to, amount);
             Ok(())
         }
 
         #[ink(message)]
-        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
-            let caller = self.env().caller();
+        pub fn reject_large_transfer(&mut self, id: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.pending_large_transfers
+                .take(id)
+                .ok_or(Error::UnknownLargeTransfer)?;
+            self.env().emit_event(LargeTransferRejected { id });
+            Ok(())
+        }
 
-            if self.paused {
-                return Err(Error::ContractPaused);
-            }
+        #[ink(message)]
+        pub fn pending_large_transfer(&self, id: u64) -> Option<TransferRecord> {
+            self.pending_large_transfers.get(id)
+        }
 
-            if amount == 0 {
+        /// Escrows `token_amount` of the caller's tokens in the contract, claimable by
+        /// `counterparty` for exactly `native_price` until `expiry` (ms timestamp).
+        #[ink(message)]
+        pub fn create_swap(
+            &mut self,
+            counterparty: AccountId,
+            token_amount: u128,
+            native_price: Balance,
+            expiry: Timestamp,
+        ) -> Result<u64> {
+            let seller = self.env().caller();
+
+            if token_amount == 0 {
                 return Err(Error::ZeroAmount);
             }
 
-            if self.allowances.get((from, caller)).unwrap_or(0) < amount {
-                return Err(Error::InsufficientAllowance);
+            if expiry <= self.env().block_timestamp() {
+                return Err(Error::SwapExpired);
             }
 
-            let from_balance = self.balance_of(from);
-            if from_balance < amount {
-                return Err(Error::InsufficientBalance);
-            }
+            self.check_transfer(seller, counterparty, token_amount)?;
 
-            let to_balance = self.balance_of(to);
-            let new_to_balance = to_balance.saturating_add(amount);
-            self.balances.insert(to, &new_to_balance);
+            self.escrow_in(seller, token_amount)?;
 
-            let from_balance = from_balance.saturating_sub(amount);
-            self.balances.insert(from, &from_balance);
-            
-            self.env().emit_event(Transfer { from, to, amount });
-            Ok(())
+            let id = self.next_swap_id;
+            self.next_swap_id = id.saturating_add(1);
+            self.swaps.insert(
+                id,
+                &Swap { seller, counterparty, token_amount, native_price, expiry },
+            );
+            self.env().emit_event(SwapCreated {
+                id,
+                seller,
+                counterparty,
+                token_amount,
+                native_price,
+                expiry,
+            });
+            Ok(id)
         }
 
-        #[ink(message)]
-        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
-            self.allowances.get((owner, spender)).unwrap_or(0)
-        }
+        /// Pays the seller the attached native value and releases the escrowed tokens
+        /// to the caller, who must be the swap's counterparty.
+        #[ink(message, payable)]
+        pub fn accept_swap(&mut self, id: u64) -> Result<()> {
+            let buyer = self.env().caller();
+            let swap = self.swaps.get(id).ok_or(Error::UnknownSwap)?;
 
-        #[ink(message)]
-        pub fn batch_transfer(&mut self, recipients: Vec<AccountId>, amounts: Vec<u128>) -> Result<()> {
-            let caller = self.env().caller();
+            if buyer != swap.counterparty {
+                return Err(Error::NotSwapParty);
+            }
 
-            if self.paused {
-                return Err(Error::ContractPaused);
+            if self.env().block_timestamp() > swap.expiry {
+                return Err(Error::SwapExpired);
             }
 
-            if recipients.len() != amounts.len() {
-                return Err(Error::InvalidBatchOperation);
+            if self.env().transferred_value() != swap.native_price {
+                return Err(Error::IncorrectPayment);
             }
 
-            let total_amount: u128 = amounts.iter().sum();
+            self.check_escrow_release(swap.seller, buyer, swap.token_amount)?;
 
-            if total_amount > self.balance_of(caller) {
-                return Err(Error::InsufficientBalance);
+            self.swaps.remove(id);
+            self.escrow_out(buyer, swap.token_amount)?;
+            self.emit_transfer(swap.seller, buyer, swap.token_amount);
+            self.env().emit_event(SwapAccepted { id });
+
+            if swap.native_price > 0 {
+                self.env()
+                    .transfer(swap.seller, swap.native_price)
+                    .map_err(|_| Error::TransferFailed)?;
             }
-            
-            for (i, recipient) in recipients.iter().enumerate() {
-                self.transfer(*recipient, amounts[i])?;
+            Ok(())
+        }
+
+        /// Returns the escrowed tokens to the seller once the swap has expired.
+        #[ink(message)]
+        pub fn cancel_swap(&mut self, id: u64) -> Result<()> {
+            let swap = self.swaps.get(id).ok_or(Error::UnknownSwap)?;
+
+            if self.env().caller() != swap.seller {
+                return Err(Error::NotSwapParty);
+            }
+
+            if self.env().block_timestamp() <= swap.expiry {
+                return Err(Error::SwapNotExpired);
             }
 
+            self.swaps.remove(id);
+            self.escrow_out(swap.seller, swap.token_amount)?;
+            self.env().emit_event(SwapCancelled { id });
             Ok(())
-            
         }
 
         #[ink(message)]
-        pub fn toggle_pause(&mut self, paused: bool) -> Result<()> {
+        pub fn swap(&self, id: u64) -> Option<Swap> {
+            self.swaps.get(id)
+        }
+
+        /// Opts the caller into two-step transfers: anything above `threshold` must go
+        /// through `queue_self_transfer` and `confirm_self_transfer`. Lowering the
+        /// threshold applies at once; raising it only applies after the caller's
+        /// current delay, so a compromised key can't switch the protection off.
+        #[ink(message)]
+        pub fn set_self_confirm_threshold(&mut self, threshold: u128) -> Result<()> {
             let caller = self.env().caller();
-            if caller != self.owner {
-                return Err(Error::NotOwner);
+            let current = self.self_confirm_threshold(caller);
+            self.pending_self_confirm_thresholds.remove(caller);
+            if threshold <= current {
+                self.self_confirm_thresholds.insert(caller, &threshold);
+            } else {
+                self.self_confirm_thresholds.insert(caller, &current);
+                let effective_at = self.self_confirm_change_at(caller);
+                self.pending_self_confirm_thresholds
+                    .insert(caller, &(threshold, effective_at));
             }
-            self.paused = paused;
-            self.env().emit_event(Paused { paused });
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn self_confirm_threshold(&self, account: AccountId) -> u128 {
+            match self.pending_self_confirm_thresholds.get(account) {
+                Some((threshold, effective_at)) if self.env().block_timestamp() >= effective_at => {
+                    threshold
+                }
+                _ => self.self_confirm_thresholds.get(account).unwrap_or(u128::MAX),
+            }
+        }
 
+        /// Wait between queueing and confirming the caller's own large transfers.
+        /// Like the threshold, a longer delay applies at once and a shorter one only
+        /// after the current delay.
         #[ink(message)]
-        pub fn burn(&mut self, amount: u128) -> Result<()> {
+        pub fn set_self_confirm_delay(&mut self, secs: u64) -> Result<()> {
             let caller = self.env().caller();
+            let current = self.self_confirm_delay(caller);
+            self.pending_self_confirm_delays.remove(caller);
+            if secs >= current {
+                self.self_confirm_delays.insert(caller, &secs);
+            } else {
+                self.self_confirm_delays.insert(caller, &current);
+                let effective_at = self.self_confirm_change_at(caller);
+                self.pending_self_confirm_delays.insert(caller, &(secs, effective_at));
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn self_confirm_delay(&self, account: AccountId) -> u64 {
+            match self.pending_self_confirm_delays.get(account) {
+                Some((secs, effective_at)) if self.env().block_timestamp() >= effective_at => secs,
+                _ => self.self_confirm_delays.get(account).unwrap_or(0),
+            }
+        }
+
+        /// Queues a transfer above the caller's self-confirm threshold; it can be
+        /// confirmed once the caller's delay has passed.
+        #[ink(message)]
+        pub fn queue_self_transfer(&mut self, to: AccountId, amount: u128) -> Result<u64> {
+            let from = self.env().caller();
+
             if amount == 0 {
                 return Err(Error::ZeroAmount);
             }
 
-            let balance = self.balance_of(caller);
-            if balance < amount {
+            if self.balance_of(from) < amount {
                 return Err(Error::InsufficientBalance);
             }
 
-            self.balances.insert(caller, &balance.saturating_sub(amount));
-            self.total_supply = self.total_supply.saturating_sub(amount);
+            let ready_at = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.self_confirm_delay(from).saturating_mul(1000));
+            let id = self.next_self_transfer_id;
+            self.next_self_transfer_id = id.saturating_add(1);
+            self.pending_self_transfers.insert(id, &((from, to, amount), ready_at));
+            Ok(id)
+        }
+
+        /// Executes one of the caller's queued transfers, re-running every other
+        /// `transfer` check.
+        #[ink(message)]
+        pub fn confirm_self_transfer(&mut self, id: u64) -> Result<()> {
+            let ((from, to, amount), ready_at) = self.own_self_transfer(id)?;
+
+            if self.env().block_timestamp() < ready_at {
+                return Err(Error::SelfTransferNotReady);
+            }
 
-            self.env().emit_event(Burned { from: caller, amount });
+            self.pending_self_transfers.remove(id);
+            self.check_confirmed_transfer(from, to, amount)?;
+            self.ensure_min_balance_left(from, amount)?;
+            self.apply_transfer(from, to, amount)?;
+            self.emit_transfer(from, to, amount);
             Ok(())
         }
 
         #[ink(message)]
-        pub fn toggle_blacklist(&mut self, account: AccountId, status: bool) -> Result<()> {
-            let caller = self.env().caller();
-            if caller != self.owner {
-                return Err(Error::NotOwner);
-            }
-            self.blacklist.insert(account, &status);
-            self.env().emit_event(Blacklisted { account, status });
+        pub fn cancel_self_transfer(&mut self, id: u64) -> Result<()> {
+            self.own_self_transfer(id)?;
+            self.pending_self_transfers.remove(id);
             Ok(())
         }
+
         #[ink(message)]
-        pub fn balance_of(&self, account: AccountId) -> u128 {
-            self.balances.get(account).unwrap_or(0)
+        pub fn pending_self_transfer(&self, id: u64) -> Option<(TransferRecord, Timestamp)> {
+            self.pending_self_transfers.get(id)
         }
 
         #[ink(message)]
-        pub fn total_supply(&self) -> u128 {
-            self.total_supply
+        pub fn set_large_transfer_threshold(&mut self, threshold: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.large_transfer_threshold = threshold;
+            Ok(())
         }
 
         #[ink(message)]
-        pub fn owner(&self) -> AccountId {
-            self.owner
+        pub fn large_transfer_threshold(&self) -> u128 {
+            self.large_transfer_threshold
+        }
+
+        /// Like `transfer`, but a contract recipient must first answer `true` to a
+        /// `can_receive()` probe.
+        #[ink(message)]
+        pub fn safe_transfer(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            if self.env().is_contract(&to) {
+                let accepted = build_call::<Environment>()
+                    .call(to)
+                    .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "can_receive"
+                    ))))
+                    .returns::<bool>()
+                    .try_invoke();
+                if !matches!(accepted, Ok(Ok(true))) {
+                    return Err(Error::TransferRejected);
+                }
+            }
+            self.transfer(to, amount)
         }
 
+        /// Sends `amount` to `to` and `tip` to `tip_recipient` atomically; the caller
+        /// must cover both. A zero tip skips the second leg.
+        #[ink(message)]
+        pub fn transfer_with_tip(
+            &mut self,
+            to: AccountId,
+            amount: u128,
+            tip: u128,
+            tip_recipient: AccountId,
+        ) -> Result<()> {
+            let from = self.env().caller();
+
+            let total = amount.checked_add(tip).ok_or(Error::Overflow)?;
+            if self.balance_of(from) < total {
+                return Err(Error::InsufficientBalance);
+            }
+            self.ensure_min_balance_left(from, total)?;
+
+            self.move_tokens_leg(from, to, amount)?;
+            self.emit_transfer(from, to, amount);
+
+            if tip > 0 {
+                self.move_tokens_leg(from, tip_recipient, tip)?;
+                self.emit_transfer(from, tip_recipient, tip);
+            }
+            Ok(())
+        }
+
+        /// Transfers and records a `(from, to, amount)` ledger entry under `invoice_id`.
+        /// Each invoice id can only be settled once.
+        #[ink(message)]
+        pub fn transfer_with_invoice(&mut self, to: AccountId, amount: u128, invoice_id: u64) -> Result<()> {
+            if self.invoices.contains(invoice_id) {
+                return Err(Error::DuplicateInvoice);
+            }
+            let from = self.env().caller();
+            self.transfer(to, amount)?;
+            self.invoices.insert(invoice_id, &(from, to, amount));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn invoice(&self, invoice_id: u64) -> Option<TransferRecord> {
+            self.invoices.get(invoice_id)
+        }
+
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, amount: u128) -> Result<()> {
+            let owner = self.env().caller();
+
+            if self.is_paused(PauseFlags::APPROVALS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.block_self_approval && spender == owner {
+                return Err(Error::SelfApproval);
+            }
+
+            if amount > self.max_allowance {
+                return Err(Error::AllowanceTooHigh);
+            }
+
+            self.set_allowance(owner, spender, amount);
+
+            self.env().emit_event(Approval { owner, spender, amount });
+            Ok(())
+        }
+
+        /// Compare-and-swap approval: only applies `new_amount` if the stored allowance
+        /// still equals `expected_current`.
+        #[ink(message)]
+        pub fn approve_if_current(
+            &mut self,
+            spender: AccountId,
+            expected_current: u128,
+            new_amount: u128,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            if self.allowance(owner, spender) != expected_current {
+                return Err(Error::AllowanceChanged);
+            }
+            self.approve(spender, new_amount)
+        }
+
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            self.ensure_allowance_spending_open(caller)?;
+
+            let allowance = self.allowance(from, caller);
+            if allowance < amount {
+                return Err(Error::InsufficientAllowance);
+            }
+
+            self.ensure_min_balance_left(from, amount)?;
+            self.record_spender_volume(caller, amount)?;
+            self.move_tokens_from(from, to, amount)?;
+            self.spend_allowance(from, caller, allowance, amount);
+
+            self.emit_transfer(from, to, amount);
+            Ok(())
+        }
+
+        /// Funds many recipients from `from` through a single allowance held by the
+        /// caller. The allowance must cover the whole batch and is spent once; any
+        /// failing leg reverts the entire distribution.
+        #[ink(message)]
+        pub fn distribute_from(
+            &mut self,
+            from: AccountId,
+            recipients: Vec<AccountId>,
+            amounts: Vec<u128>,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            self.ensure_allowance_spending_open(caller)?;
+
+            if recipients.len() != amounts.len() || recipients.len() > self.max_batch_size as usize {
+                return Err(Error::InvalidBatchOperation);
+            }
+
+            let total = amounts
+                .iter()
+                .try_fold(0u128, |acc, amount| acc.checked_add(*amount))
+                .ok_or(Error::Overflow)?;
+
+            let allowance = self.allowance(from, caller);
+            if allowance < total {
+                return Err(Error::InsufficientAllowance);
+            }
+
+            self.ensure_min_balance_left(from, total)?;
+            self.record_spender_volume(caller, total)?;
+
+            for (recipient, amount) in recipients.into_iter().zip(amounts) {
+                self.move_tokens_from(from, recipient, amount)?;
+                self.emit_transfer(from, recipient, amount);
+            }
+
+            self.spend_allowance(from, caller, allowance, total);
+            Ok(())
+        }
+
+        /// Caps how much any one spender can move through `transfer_from` and
+        /// `distribute_from` per 24h window; `u128::MAX` disables the cap.
+        #[ink(message)]
+        pub fn set_spender_daily_limit(&mut self, limit: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.spender_daily_limit = limit;
+            Ok(())
+        }
+
+        /// Volume `spender` has moved in its current 24h window.
+        #[ink(message)]
+        pub fn spender_daily_spent(&self, spender: AccountId) -> u128 {
+            match self.spender_daily_spent.get(spender) {
+                Some((start, spent))
+                    if self.env().block_timestamp() < start.saturating_add(DAY_MS) =>
+                {
+                    spent
+                }
+                _ => 0,
+            }
+        }
+
+        /// Lets `spender` pull `amount_per_period` from the caller once per
+        /// `period_secs`, without a standing allowance. Replaces any existing terms.
+        #[ink(message)]
+        pub fn create_subscription(
+            &mut self,
+            spender: AccountId,
+            amount_per_period: u128,
+            period_secs: u64,
+        ) -> Result<()> {
+            if amount_per_period == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if period_secs == 0 {
+                return Err(Error::ZeroPeriod);
+            }
+            let owner = self.env().caller();
+            self.subscriptions.insert(
+                (owner, spender),
+                &Subscription { amount_per_period, period_secs, last_pull_at: None },
+            );
+            Ok(())
+        }
+
+        /// Called by the subscribed spender to collect the current period's payment.
+        /// Pulls are allowance-based spends: they honour `allowances_frozen` and count
+        /// toward the spender's daily limit.
+        #[ink(message)]
+        pub fn pull_subscription(&mut self, owner: AccountId) -> Result<()> {
+            let spender = self.env().caller();
+            self.ensure_allowance_spending_open(spender)?;
+
+            let mut subscription = self
+                .subscriptions
+                .get((owner, spender))
+                .ok_or(Error::NoSubscription)?;
+
+            let now = self.env().block_timestamp();
+            if let Some(last) = subscription.last_pull_at {
+                if now < last.saturating_add(subscription.period_secs.saturating_mul(1000)) {
+                    return Err(Error::SubscriptionNotDue);
+                }
+            }
+
+            let amount = subscription.amount_per_period;
+            self.ensure_min_balance_left(owner, amount)?;
+            self.record_spender_volume(spender, amount)?;
+            self.move_tokens_from(owner, spender, amount)?;
+            subscription.last_pull_at = Some(now);
+            self.subscriptions.insert((owner, spender), &subscription);
+
+            self.emit_transfer(owner, spender, amount);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cancel_subscription(&mut self, spender: AccountId) -> Result<()> {
+            let owner = self.env().caller();
+            self.subscriptions
+                .take((owner, spender))
+                .ok_or(Error::NoSubscription)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn subscription(&self, owner: AccountId, spender: AccountId) -> Option<Subscription> {
+            self.subscriptions.get((owner, spender))
+        }
+
+        /// Grants `spender` a one-shot allowance of `amount` that is consumed on the
+        /// spot by moving the tokens to `to`. The grant goes through the `approve`
+        /// checks and the move through the caller's own `transfer` ones; `spender`
+        /// never signs, so it is neither charged nor able to lend its exemptions.
+        /// Nothing is written to `allowances`, so no residual approval is left behind
+        /// and any existing one is untouched.
+        #[ink(message)]
+        pub fn transfer_with_temp_approval(
+            &mut self,
+            spender: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+
+            if self.is_paused(PauseFlags::APPROVALS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.block_self_approval && spender == owner {
+                return Err(Error::SelfApproval);
+            }
+
+            if amount > self.max_allowance {
+                return Err(Error::AllowanceTooHigh);
+            }
+
+            if self.allowances_frozen {
+                return Err(Error::AllowancesFrozen);
+            }
+
+            self.move_tokens(owner, to, amount)?;
+            self.emit_transfer(owner, to, amount);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Sum of all allowances `owner` currently grants, or `u128::MAX` while any of
+        /// them is infinite. Infinite grants are counted apart from the finite sum, so
+        /// revoking one brings back the exact total of the rest.
+        #[ink(message)]
+        pub fn total_allowance_granted(&self, owner: AccountId) -> u128 {
+            if self.infinite_allowances_granted.get(owner).unwrap_or(0) > 0 {
+                return u128::MAX;
+            }
+            self.total_allowance_granted.get(owner).unwrap_or(0)
+        }
+
+        /// Sum of all allowances `spender` currently holds across owners, with the
+        /// same treatment of infinite approvals.
+        #[ink(message)]
+        pub fn total_allowance_received(&self, spender: AccountId) -> u128 {
+            if self.infinite_allowances_received.get(spender).unwrap_or(0) > 0 {
+                return u128::MAX;
+            }
+            self.allowance_received.get(spender).unwrap_or(0)
+        }
+
+        /// All-or-nothing: entries are applied in order, and the first failing entry's
+        /// error reverts the whole message, including transfers already applied.
+        #[ink(message)]
+        pub fn batch_transfer(&mut self, recipients: Vec<AccountId>, amounts: Vec<u128>) -> Result<()> {
+            let caller = self.env().caller();
+
+            if self.is_paused(PauseFlags::TRANSFERS) && !self.is_pause_exempt(caller) {
+                return Err(Error::ContractPaused);
+            }
+
+            if !self.transfers_enabled {
+                return Err(Error::TransfersDisabled);
+            }
+
+            if self.soulbound {
+                return Err(Error::Soulbound);
+            }
+
+            if recipients.len() != amounts.len() {
+                return Err(Error::InvalidBatchOperation);
+            }
+
+            if amounts.iter().any(|amount| *amount < self.min_transfer_amount) {
+                return Err(Error::BelowMinimum);
+            }
+
+            let total_amount: u128 = amounts.iter().sum();
+
+            if total_amount > self.balance_of(caller) {
+                return Err(Error::InsufficientBalance);
+            }
+            self.ensure_min_balance_left(caller, total_amount)?;
+
+            self.check_batch_entries(caller, &recipients, &amounts)
+                .map_err(|(_, error)| error)?;
+            
+            for (i, recipient) in recipients.iter().enumerate() {
+                let amount = amounts[i];
+                self.move_tokens_leg(caller, *recipient, amount)?;
+                if self.compact_batch_events {
+                    self.record_recent_transfer(caller, *recipient, amount);
+                } else {
+                    self.emit_transfer(caller, *recipient, amount);
+                }
+            }
+
+            if self.compact_batch_events {
+                self.env().emit_event(BatchTransfer {
+                    from: caller,
+                    count: recipients.len() as u32,
+                    total: total_amount,
+                });
+            }
+
+            Ok(())
+            
+        }
+
+        /// Dry run of `batch_transfer`'s per-entry checks: the first failing entry's
+        /// index and error, or `Ok(())` if every entry would pass on its own.
+        #[ink(message)]
+        pub fn validate_batch_transfer(
+            &self,
+            from: AccountId,
+            recipients: Vec<AccountId>,
+            amounts: Vec<u128>,
+        ) -> core::result::Result<(), (u32, Error)> {
+            self.check_batch_entries(from, &recipients, &amounts)
+        }
+
+        /// Keccak-256 commitment over a `batch_transfer` payload, so a relayer can
+        /// confirm the batch matches what was signed before submitting it.
+        #[ink(message)]
+        pub fn batch_hash(&self, recipients: Vec<AccountId>, amounts: Vec<u128>) -> [u8; 32] {
+            self.env()
+                .hash_encoded::<ink::env::hash::Keccak256, _>(&(recipients, amounts))
+        }
+
+        /// Deterministic sub-account id: the Keccak-256 hash of `(base, index)`.
+        #[ink(message)]
+        pub fn derive_subaccount(&self, base: AccountId, index: u32) -> AccountId {
+            AccountId::from(
+                self.env()
+                    .hash_encoded::<ink::env::hash::Keccak256, _>(&(base, index)),
+            )
+        }
+
+        /// Convenience switch that sets or clears every pause flag at once.
+        #[ink(message)]
+        pub fn toggle_pause(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.pause_flags = if paused { PauseFlags::ALL } else { 0 };
+            self.env().emit_event(Paused { paused });
+            Ok(())
+        }
+
+        /// Pauses individual operation groups; see `PauseFlags` for the bit layout.
+        #[ink(message)]
+        pub fn set_pause_flags(&mut self, flags: u8) -> Result<()> {
+            self.ensure_owner()?;
+            self.pause_flags = flags & PauseFlags::ALL;
+            self.env().emit_event(PauseFlagsSet { flags: self.pause_flags });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn pause_flags(&self) -> u8 {
+            self.pause_flags
+        }
+
+
+        /// Lets the backup guardian pause once the owner has gone `inactivity_secs`
+        /// without any owner-only call. The switch stays off until the owner sets a
+        /// nonzero `inactivity_secs`.
+        #[ink(message)]
+        pub fn emergency_pause(&mut self) -> Result<()> {
+            if self.backup_guardian != Some(self.env().caller()) {
+                return Err(Error::NotGuardian);
+            }
+            if self.inactivity_secs == 0 {
+                return Err(Error::InactivityNotSet);
+            }
+            let idle = self.env().block_timestamp().saturating_sub(self.last_owner_action);
+            if idle <= self.inactivity_secs.saturating_mul(1000) {
+                return Err(Error::OwnerStillActive);
+            }
+            self.pause_flags = PauseFlags::ALL;
+            self.env().emit_event(Paused { paused: true });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_backup_guardian(&mut self, guardian: Option<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            self.backup_guardian = guardian;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn backup_guardian(&self) -> Option<AccountId> {
+            self.backup_guardian
+        }
+
+        #[ink(message)]
+        pub fn set_inactivity_secs(&mut self, secs: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.inactivity_secs = secs;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn inactivity_secs(&self) -> u64 {
+            self.inactivity_secs
+        }
+
+        #[ink(message)]
+        pub fn last_owner_action(&self) -> Timestamp {
+            self.last_owner_action
+        }
+
+        /// Disables peer-to-peer transfers only; mint, burn and admin calls keep working.
+        #[ink(message)]
+        pub fn set_transfers_enabled(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.transfers_enabled = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfers_enabled(&self) -> bool {
+            self.transfers_enabled
+        }
+
+        /// Emergency stop for allowance-based spending only; direct transfers and
+        /// burns keep working.
+        #[ink(message)]
+        pub fn set_allowances_frozen(&mut self, frozen: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.allowances_frozen = frozen;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn allowances_frozen(&self) -> bool {
+            self.allowances_frozen
+        }
+
+        #[ink(message)]
+        pub fn set_allow_zero_transfers(&mut self, allowed: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.allow_zero_transfers = allowed;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn allow_zero_transfers(&self) -> bool {
+            self.allow_zero_transfers
+        }
+
+        /// Smallest nonzero balance any send may leave the sender with; 0 disables the
+        /// check.
+        #[ink(message)]
+        pub fn set_min_balance(&mut self, min_balance: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_balance = min_balance;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_balance(&self) -> u128 {
+            self.min_balance
+        }
+
+        /// When on, a `transfer` that would strand dust below `min_balance` sends the
+        /// dust along with it instead of failing with `BelowMinBalance`.
+        #[ink(message)]
+        pub fn set_dust_sweep(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.dust_sweep = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn dust_sweep(&self) -> bool {
+            self.dust_sweep
+        }
+
+        /// Turns the bounded on-chain log of the last 16 transfers on or off.
+        #[ink(message)]
+        pub fn set_record_recent_transfers(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.record_recent_transfers = enabled;
+            Ok(())
+        }
+
+        /// Up to the last 16 recorded transfers, newest first. Batch legs are listed
+        /// even when compact batch events leave out their `Transfer` events.
+        #[ink(message)]
+        pub fn recent_transfers(&self) -> Vec<RecentTransfer> {
+            (1..=self.recent_transfers_len)
+                .filter_map(|back| {
+                    let slot = (self.recent_transfers_next + RECENT_TRANSFERS_CAPACITY - back)
+                        % RECENT_TRANSFERS_CAPACITY;
+                    self.recent_transfers.get(slot)
+                })
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn set_pause_exempt(&mut self, account: AccountId, exempt: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.pause_exempt.insert(account, &exempt);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_pause_exempt(&self, account: AccountId) -> bool {
+            self.pause_exempt.get(account).unwrap_or(false)
+        }
+
+        /// Replaces the account's `ExemptionFlags` bits; each set bit waives one restriction.
+        #[ink(message)]
+        pub fn set_exemption_flags(&mut self, account: AccountId, flags: u8) -> Result<()> {
+            self.ensure_owner()?;
+            self.exemption_flags.insert(account, &flags);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn exemption_flags(&self, account: AccountId) -> u8 {
+            self.exemption_flags.get(account).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn burn(&mut self, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+
+            if self.is_paused(PauseFlags::BURNS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let balance = self.balance_of(caller);
+            if balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.set_balance(caller, balance.saturating_sub(amount));
+            self.total_supply = self.total_supply.saturating_sub(amount);
+
+            self.emit_burned(caller, amount);
+            Ok(())
+        }
+
+        /// Burns the caller's whole balance; fails with `ZeroAmount` if it is empty.
+        #[ink(message)]
+        pub fn burn_all(&mut self) -> Result<()> {
+            let balance = self.balance_of(self.env().caller());
+            self.burn(balance)
+        }
+
+        /// Burns the caller's tokens for release on `dest_chain`; relayers pick up the
+        /// `BridgeBurn` event, whose nonce is unique per burn. Blacklisted accounts
+        /// cannot bridge out.
+        #[ink(message)]
+        pub fn bridge_burn(
+            &mut self,
+            amount: u128,
+            dest_chain: u32,
+            dest_address: [u8; 32],
+        ) -> Result<()> {
+            if self.is_blacklisted(self.env().caller()) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            self.burn(amount)?;
+
+            let nonce = self.bridge_nonce;
+            self.bridge_nonce = nonce.saturating_add(1);
+            self.env().emit_event(BridgeBurn {
+                from: self.env().caller(),
+                amount,
+                dest_chain,
+                dest_address,
+                nonce,
+            });
+            Ok(())
+        }
+
+        /// Nonce the next `bridge_burn` will carry.
+        #[ink(message)]
+        pub fn bridge_nonce(&self) -> u64 {
+            self.bridge_nonce
+        }
+
+        /// Mints the counterpart of a `bridge_burn` observed on `src_chain`. Each
+        /// `(src_chain, src_nonce)` can be minted only once.
+        #[ink(message)]
+        pub fn bridge_mint(
+            &mut self,
+            to: AccountId,
+            amount: u128,
+            src_chain: u32,
+            src_nonce: u64,
+        ) -> Result<()> {
+            if self.bridge_relayer != Some(self.env().caller()) {
+                return Err(Error::NotRelayer);
+            }
+
+            if self.is_paused(PauseFlags::MINTS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.processed_bridge_mints.contains((src_chain, src_nonce)) {
+                return Err(Error::AlreadyProcessed);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            self.ensure_mint_recipient(to, amount)?;
+            self.ensure_reserves_cover(amount)?;
+            let new_balance = self.balance_of(to).saturating_add(amount);
+            self.record_period_growth(self.env().block_timestamp(), amount)?;
+            self.set_balance(to, new_balance);
+            self.start_hold_period(to, amount);
+            self.total_supply = self.total_supply.checked_add(amount).ok_or(Error::Overflow)?;
+            self.processed_bridge_mints.insert((src_chain, src_nonce), &true);
+
+            self.emit_minted(to, amount);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_bridge_relayer(&mut self, relayer: Option<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            self.bridge_relayer = relayer;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn bridge_relayer(&self) -> Option<AccountId> {
+            self.bridge_relayer
+        }
+
+        #[ink(message)]
+        pub fn is_bridge_mint_processed(&self, src_chain: u32, src_nonce: u64) -> bool {
+            self.processed_bridge_mints.contains((src_chain, src_nonce))
+        }
+
+        /// Burns `amount` and pays the caller the same share of the contract's native
+        /// balance (`native * amount / total_supply`). State is settled before the
+        /// native transfer goes out. Blacklisted accounts cannot cash out this way.
+        #[ink(message)]
+        pub fn rage_quit(&mut self, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+
+            if self.is_paused(PauseFlags::BURNS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.is_blacklisted(caller) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let balance = self.balance_of(caller);
+            if balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let payout = mul_div(self.env().balance(), amount, self.total_supply)
+                .ok_or(Error::Overflow)?;
+
+            self.set_balance(caller, balance.saturating_sub(amount));
+            self.total_supply = self.total_supply.saturating_sub(amount);
+            self.emit_burned(caller, amount);
+
+            if payout > 0 {
+                self.env().transfer(caller, payout).map_err(|_| Error::TransferFailed)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn toggle_blacklist(&mut self, account: AccountId, status: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.ensure_blacklistable(account, status)?;
+            self.set_blacklist_status(account, status);
+            self.notify_compliance_hook(account, status);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn batch_set_blacklist(&mut self, accounts: Vec<AccountId>, status: bool) -> Result<()> {
+            self.ensure_owner()?;
+
+            if accounts.len() > self.max_batch_size as usize {
+                return Err(Error::InvalidBatchOperation);
+            }
+
+            for account in accounts {
+                self.ensure_blacklistable(account, status)?;
+                self.set_blacklist_status(account, status);
+                self.notify_compliance_hook(account, status);
+            }
+            Ok(())
+        }
+
+        /// Contract sent `on_blacklist(account, status)` after every blacklist change.
+        #[ink(message)]
+        pub fn set_compliance_hook(&mut self, hook: Option<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            self.compliance_hook = hook;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn compliance_hook(&self) -> Option<AccountId> {
+            self.compliance_hook
+        }
+
+        /// Moves a blacklisted account's entire position, balance and stake, to `to`;
+        /// only available in `BlacklistAction::Seize` mode. Unclaimed staking rewards
+        /// are forfeited.
+        #[ink(message)]
+        pub fn seize(&mut self, account: AccountId, to: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            if self.blacklist_action != BlacklistAction::Seize {
+                return Err(Error::SeizureDisabled);
+            }
+
+            if !self.is_blacklisted(account) {
+                return Err(Error::NotBlacklisted);
+            }
+
+            let staked = self.staked_balance(account);
+            if staked > 0 {
+                self.update_pool();
+                self.stakes.remove(account);
+                self.reward_debt.remove(account);
+                self.deferred_rewards.remove(account);
+                self.total_staked = self.total_staked.saturating_sub(staked);
+                self.escrow_out(account, staked)?;
+                self.env().emit_event(Unstaked { account, amount: staked });
+            }
+
+            let amount = self.balance_of(account);
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            self.apply_transfer(account, to, amount)?;
+            self.emit_transfer(account, to, amount);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_blacklist_action(&mut self, action: BlacklistAction) -> Result<()> {
+            self.ensure_owner()?;
+            self.blacklist_action = action;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn blacklist_action(&self) -> BlacklistAction {
+            self.blacklist_action
+        }
+
+        #[ink(message)]
+        pub fn is_blacklisted(&self, account: AccountId) -> bool {
+            self.blacklist.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn set_blacklist_grace_secs(&mut self, secs: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.blacklist_grace_secs = secs;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn blacklist_grace_secs(&self) -> u64 {
+            self.blacklist_grace_secs
+        }
+
+        #[ink(message)]
+        pub fn set_whitelist_mode(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist_mode = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn whitelist_mode(&self) -> bool {
+            self.whitelist_mode
+        }
+
+        #[ink(message)]
+        pub fn set_whitelisted(&mut self, account: AccountId, status: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist.insert(account, &status);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_whitelisted(&self, account: AccountId) -> bool {
+            self.whitelist.get(account).unwrap_or(false)
+        }
+
+        /// When on, `mint` only credits accounts on the mint allowlist. Independent
+        /// of the transfer whitelist.
+        #[ink(message)]
+        pub fn set_mint_allowlist_enabled(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.mint_allowlist_enabled = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_mint_allowlisted(&mut self, account: AccountId, status: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.mint_allowlist.insert(account, &status);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_mint_allowlisted(&self, account: AccountId) -> bool {
+            self.mint_allowlist.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, account: AccountId) -> u128 {
+            self.balances.get(account).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> u128 {
+            self.total_supply
+        }
+
+        /// Checked sum of `accounts`' balances; duplicates are counted each time.
+        #[ink(message)]
+        pub fn group_balance(&self, accounts: Vec<AccountId>) -> Result<u128> {
+            accounts.iter().try_fold(0u128, |total, account| {
+                total
+                    .checked_add(self.balance_of(*account))
+                    .ok_or(Error::Overflow)
+            })
+        }
+
+        /// Storage cells keyed by `account` alone (balance, stake, flags and the like).
+        /// Pairwise entries such as individual allowances and sub-ledger balances
+        /// can't be enumerated on-chain and are not counted.
+        #[ink(message)]
+        pub fn account_storage_cells(&self, account: AccountId) -> u32 {
+            [
+                self.balances.contains(account),
+                self.blacklist.contains(account),
+                self.blacklisted_at.contains(account),
+                self.pause_exempt.contains(account),
+                self.total_allowance_granted.contains(account),
+                self.allowance_received.contains(account),
+                self.infinite_allowances_granted.contains(account),
+                self.infinite_allowances_received.contains(account),
+                self.whitelist.contains(account),
+                self.stakes.contains(account),
+                self.reward_debt.contains(account),
+                self.deferred_rewards.contains(account),
+                self.exemption_flags.contains(account),
+                self.spender_daily_spent.contains(account),
+                self.self_confirm_thresholds.contains(account),
+                self.self_confirm_delays.contains(account),
+                self.pending_self_confirm_thresholds.contains(account),
+                self.pending_self_confirm_delays.contains(account),
+                self.hold_tranches.contains(account),
+                self.mint_allowlist.contains(account),
+            ]
+            .iter()
+            .filter(|present| **present)
+            .count() as u32
+        }
+
+        /// Balance expressed in display units (`balance_of / unit_scale`).
+        #[ink(message)]
+        pub fn balance_of_scaled(&self, account: AccountId) -> u128 {
+            self.balance_of(account) / self.unit_scale
+        }
+
+        #[ink(message)]
+        pub fn total_supply_scaled(&self) -> u128 {
+            self.total_supply / self.unit_scale
+        }
+
+        #[ink(message)]
+        pub fn set_unit_scale(&mut self, scale: u128) -> Result<()> {
+            self.ensure_owner()?;
+            if scale == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.unit_scale = scale;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unit_scale(&self) -> u128 {
+            self.unit_scale
+        }
+
+        #[ink(message)]
+        pub fn soulbound(&self) -> bool {
+            self.soulbound
+        }
+
+        #[ink(message)]
+        pub fn set_prune_zero_balances(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.prune_zero_balances = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn prune_zero_balances(&self) -> bool {
+            self.prune_zero_balances
+        }
+
+        /// Number of accounts with a nonzero balance.
+        #[ink(message)]
+        pub fn holder_count(&self) -> u32 {
+            self.holder_count
+        }
+
+        /// Tokens held by the contract's own account.
+        #[ink(message)]
+        pub fn contract_balance(&self) -> u128 {
+            self.balance_of(self.env().account_id())
+        }
+
+        /// Sequence number of the last `Transfer`/`Minted`/`Burned` event.
+        #[ink(message)]
+        pub fn event_seq(&self) -> u64 {
+            self.event_seq
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// False once ownership has been renounced to the zero account.
+        #[ink(message)]
+        pub fn is_owned(&self) -> bool {
+            self.owner != AccountId::from([0u8; 32])
+        }
+
+        /// Block number and timestamp (ms) of deployment.
+        #[ink(message)]
+        pub fn created_at(&self) -> (BlockNumber, Timestamp) {
+            (self.created_at_block, self.created_at_time)
+        }
+
+        /// Permanently hands ownership to the zero account; every owner-only call
+        /// fails with `OwnershipRenounced` afterwards.
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let previous_owner = self.owner;
+            self.owner = AccountId::from([0u8; 32]);
+            self.env().emit_event(OwnershipTransferred { previous_owner, new_owner: self.owner });
+            Ok(())
+        }
+
+        /// Mints `total` across `holders` pro-rata to their current balances. Rounding
+        /// dust goes to the first holder so the shares always sum to `total`. Counts as
+        /// an owner mint: the cooldown, reserve and per-recipient `mint` checks apply.
+        #[ink(message)]
+        pub fn distribute_rewards(&mut self, holders: Vec<AccountId>, total: u128) -> Result<()> {
+            self.ensure_owner()?;
+
+            if self.is_paused(PauseFlags::MINTS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if total == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            if holders.is_empty() || holders.len() > self.max_batch_size as usize {
+                return Err(Error::InvalidBatchOperation);
+            }
+
+            let balances: Vec<u128> = holders.iter().map(|holder| self.balance_of(*holder)).collect();
+            let held = balances
+                .iter()
+                .try_fold(0u128, |acc, balance| acc.checked_add(*balance))
+                .ok_or(Error::Overflow)?;
+            if held == 0 {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let mut shares = Vec::with_capacity(holders.len());
+            for balance in &balances {
+                let share = mul_div(total, *balance, held).ok_or(Error::Overflow)?;
+                shares.push(share);
+            }
+            let distributed: u128 = shares.iter().sum();
+            shares[0] = shares[0].saturating_add(total.saturating_sub(distributed));
+
+            let now = self.env().block_timestamp();
+            self.ensure_mint_cooldown_over(now)?;
+            self.ensure_reserves_cover(total)?;
+            self.record_period_growth(now, total)?;
+            for (holder, share) in holders.iter().zip(&shares) {
+                if *share > 0 {
+                    self.ensure_mint_recipient(*holder, *share)?;
+                }
+            }
+
+            for (holder, share) in holders.into_iter().zip(shares) {
+                if share > 0 {
+                    self.credit_minted(holder, share);
+                }
+            }
+            self.last_mint_at = Some(now);
+            Ok(())
+        }
+
+        /// Mints on a fungible sub-ledger sharing this contract's admin. Token id 0 is
+        /// the primary token and goes through `mint`.
+        #[ink(message)]
+        pub fn mint_sub(&mut self, token_id: u32, to: AccountId, amount: u128) -> Result<()> {
+            if token_id == 0 {
+                return self.mint(to, amount);
+            }
+
+            self.ensure_owner()?;
+
+            if self.is_paused(PauseFlags::MINTS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.is_blacklisted(to) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let balance = self
+                .balance_of_sub(token_id, to)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            let supply = self
+                .total_supply_sub(token_id)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.sub_balances.insert((token_id, to), &balance);
+            self.sub_supplies.insert(token_id, &supply);
+
+            self.env().emit_event(SubMinted { token_id, to, amount });
+            Ok(())
+        }
+
+        /// Transfers on a sub-ledger; token id 0 goes through `transfer`.
+        #[ink(message)]
+        pub fn transfer_sub(&mut self, token_id: u32, to: AccountId, amount: u128) -> Result<()> {
+            if token_id == 0 {
+                return self.transfer(to, amount);
+            }
+
+            let from = self.env().caller();
+
+            if self.is_paused(PauseFlags::TRANSFERS) && !self.is_pause_exempt(from) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.soulbound {
+                return Err(Error::Soulbound);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            if self.is_blacklist_blocked(from) || self.is_blacklisted(to) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            let from_balance = self.balance_of_sub(token_id, from);
+            if from_balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            if from != to {
+                let to_balance = self
+                    .balance_of_sub(token_id, to)
+                    .checked_add(amount)
+                    .ok_or(Error::Overflow)?;
+                self.sub_balances.insert((token_id, from), &(from_balance - amount));
+                self.sub_balances.insert((token_id, to), &to_balance);
+            }
+
+            self.env().emit_event(SubTransfer { token_id, from, to, amount });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn balance_of_sub(&self, token_id: u32, account: AccountId) -> u128 {
+            if token_id == 0 {
+                return self.balance_of(account);
+            }
+            self.sub_balances.get((token_id, account)).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn total_supply_sub(&self, token_id: u32) -> u128 {
+            if token_id == 0 {
+                return self.total_supply;
+            }
+            self.sub_supplies.get(token_id).unwrap_or(0)
+        }
+
+        /// Escrows `amount` of the caller's tokens in the contract and starts accruing
+        /// block rewards on it. Any reward pending on an existing stake is paid out first.
+        #[ink(message)]
+        pub fn stake(&mut self, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+
+            if self.is_paused(PauseFlags::TRANSFERS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.is_blacklisted(caller) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let balance = self.balance_of(caller);
+            if balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.update_pool();
+            self.harvest(caller)?;
+
+            self.escrow_in(caller, amount)?;
+
+            let staked = self.staked_balance(caller).saturating_add(amount);
+            self.stakes.insert(caller, &staked);
+            self.total_staked = self.total_staked.saturating_add(amount);
+            self.reset_reward_debt(caller, staked);
+
+            self.env().emit_event(Staked { account: caller, amount });
+            Ok(())
+        }
+
+        /// Returns `amount` of escrowed tokens to the caller, paying out pending rewards.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let staked = self.staked_balance(caller);
+            if staked < amount {
+                return Err(Error::InsufficientStake);
+            }
+
+            self.update_pool();
+            self.harvest(caller)?;
+
+            let remaining = staked.saturating_sub(amount);
+            self.stakes.insert(caller, &remaining);
+            self.total_staked = self.total_staked.saturating_sub(amount);
+            self.reset_reward_debt(caller, remaining);
+
+            self.escrow_out(caller, amount)?;
+
+            self.env().emit_event(Unstaked { account: caller, amount });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn claim_rewards(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if self.is_blacklisted(caller) {
+                return Err(Error::AccountBlacklisted);
+            }
+            self.update_pool();
+            if self.harvest(caller)? == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.reset_reward_debt(caller, self.staked_balance(caller));
+            Ok(())
+        }
+
+        /// Moves the caller's whole position (balance and stake) to `new_account`.
+        /// Pending rewards are settled to both accounts before the stake moves, and
+        /// the position as a whole (balance plus stake) must pass every `transfer`
+        /// check, so staking first can't route around them.
+        #[ink(message)]
+        pub fn migrate_account(&mut self, new_account: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+
+            if self.is_paused(PauseFlags::TRANSFERS) {
+                return Err(Error::ContractPaused);
+            }
+
+            if self.soulbound {
+                return Err(Error::Soulbound);
+            }
+
+            if new_account == caller {
+                return Err(Error::InvalidMigrationTarget);
+            }
+
+            if self.is_blacklisted(caller) || self.is_blacklisted(new_account) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            let amount = self.balance_of(caller);
+            let staked = self.staked_balance(caller);
+            if amount == 0 && staked == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let total = amount.checked_add(staked).ok_or(Error::Overflow)?;
+
+            if staked > 0 {
+                self.update_pool();
+                self.harvest(caller)?;
+                self.harvest(new_account)?;
+                self.stakes.remove(caller);
+                self.reward_debt.remove(caller);
+                self.escrow_out(caller, staked)?;
+            }
+
+            self.check_transfer(caller, new_account, total)?;
+            self.apply_transfer(caller, new_account, total)?;
+
+            if staked > 0 {
+                self.escrow_in(new_account, staked)?;
+                let new_staked = self.staked_balance(new_account).saturating_add(staked);
+                self.stakes.insert(new_account, &new_staked);
+                self.reset_reward_debt(new_account, new_staked);
+            }
+
+            if amount > 0 {
+                self.emit_transfer(caller, new_account, amount);
+            }
+            Ok(())
+        }
+
+        /// Reward accrued by `account`, including any deferred by the growth cap, that
+        /// `claim_rewards` would mint right now if the cap allows.
+        #[ink(message)]
+        pub fn pending_reward(&self, account: AccountId) -> u128 {
+            let staked = self.staked_balance(account);
+            let accrued = staked.saturating_mul(self.current_acc_reward_per_share()) / ACC_REWARD_PRECISION;
+            accrued
+                .saturating_sub(self.reward_debt.get(account).unwrap_or(0))
+                .saturating_add(self.deferred_rewards.get(account).unwrap_or(0))
+        }
+
+        #[ink(message)]
+        pub fn staked_balance(&self, account: AccountId) -> u128 {
+            self.stakes.get(account).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn total_staked(&self) -> u128 {
+            self.total_staked
+        }
+
+        /// Settles rewards up to the current block under the old rate before switching.
+        #[ink(message)]
+        pub fn set_reward_per_block(&mut self, reward: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.update_pool();
+            self.reward_per_block = reward;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn reward_per_block(&self) -> u128 {
+            self.reward_per_block
+        }
+
+        /// Recovers native currency sent to the contract by mistake. Only the surplus
+        /// over the outstanding supply's backing can leave, so `rage_quit` payouts and
+        /// the reserve ratio stay covered.
+        #[ink(message)]
+        pub fn sweep_native(&mut self, to: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            let surplus = self
+                .env()
+                .balance()
+                .saturating_sub(self.required_native_backing()?);
+            if amount > surplus {
+                return Err(Error::InsufficientReserves);
+            }
+            self.env().transfer(to, amount).map_err(|_| Error::TransferFailed)
+        }
+
+        /// When enabled, direct transfers to the contract's own account are rejected.
+        /// Staking escrow moves balances internally and is unaffected.
+        #[ink(message)]
+        pub fn set_block_self_deposit(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.block_self_deposit = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn block_self_deposit(&self) -> bool {
+            self.block_self_deposit
+        }
+
+        #[ink(message)]
+        pub fn set_block_self_approval(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.block_self_approval = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn block_self_approval(&self) -> bool {
+            self.block_self_approval
+        }
+
+        #[ink(message)]
+        pub fn set_max_allowance(&mut self, amount: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_allowance = amount;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_allowance(&self) -> u128 {
+            self.max_allowance
+        }
+
+        /// `FeatureFlags` bits for every optional capability currently active.
+        #[ink(message)]
+        pub fn supported_features(&self) -> u64 {
+            let enabled = [
+                (FeatureFlags::STAKING_REWARDS, self.reward_per_block > 0),
+                (FeatureFlags::BRIDGING, self.bridge_relayer.is_some()),
+                (FeatureFlags::WHITELIST, self.whitelist_mode),
+                (FeatureFlags::SOULBOUND, self.soulbound),
+                (FeatureFlags::MAX_WALLET, self.max_wallet_amount != u128::MAX),
+                (
+                    FeatureFlags::LARGE_TRANSFER_QUEUE,
+                    self.large_transfer_threshold != u128::MAX,
+                ),
+                (FeatureFlags::GROWTH_CAP, self.max_growth_bps_per_period > 0),
+                (FeatureFlags::MIN_BALANCE, self.min_balance > 0),
+                (FeatureFlags::MINT_COOLDOWN, self.mint_cooldown_secs > 0),
+                (
+                    FeatureFlags::BLACKLIST_SEIZURE,
+                    self.blacklist_action == BlacklistAction::Seize,
+                ),
+                (FeatureFlags::FLASH_MINT_FEE, self.flash_mint_fee_bps > 0),
+                (FeatureFlags::RECENT_TRANSFERS, self.record_recent_transfers),
+                (FeatureFlags::RESERVE_RATIO, self.min_reserve_ratio_bps > 0),
+                (FeatureFlags::COMPLIANCE_HOOK, self.compliance_hook.is_some()),
+                (FeatureFlags::SPENDER_DAILY_LIMIT, self.spender_daily_limit != u128::MAX),
+                (FeatureFlags::HOLD_PERIOD, self.hold_secs > 0),
+                (FeatureFlags::MINT_ALLOWLIST, self.mint_allowlist_enabled),
+            ];
+            enabled
+                .iter()
+                .filter(|(_, on)| *on)
+                .fold(0, |bits, (flag, _)| bits | flag)
+        }
+
+        /// Tokens the contract holds on behalf of stakers and open swaps.
+        #[ink(message)]
+        pub fn total_escrowed(&self) -> u128 {
+            self.total_escrowed
+        }
+
+        /// Returns tokens mistakenly sent to the contract address. Only the surplus over
+        /// `total_escrowed` can leave, so stakes and swap escrow stay covered.
+        #[ink(message)]
+        pub fn rescue_self_sent(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            self.ensure_owner()?;
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let contract = self.env().account_id();
+            let surplus = self.balance_of(contract).saturating_sub(self.total_escrowed);
+            if amount > surplus {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.apply_transfer(contract, to, amount)?;
+            self.emit_transfer(contract, to, amount);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn config(&self) -> TokenConfig {
+            TokenConfig {
+                pause_flags: self.pause_flags,
+                transfers_enabled: self.transfers_enabled,
+                whitelist_mode: self.whitelist_mode,
+                block_self_deposit: self.block_self_deposit,
+                compact_batch_events: self.compact_batch_events,
+                max_wallet_amount: self.max_wallet_amount,
+                max_batch_size: self.max_batch_size,
+                blacklist_grace_secs: self.blacklist_grace_secs,
+                reward_per_block: self.reward_per_block,
+                flash_mint_fee_bps: self.flash_mint_fee_bps,
+                block_self_approval: self.block_self_approval,
+                max_allowance: self.max_allowance,
+                unit_scale: self.unit_scale,
+                allowances_frozen: self.allowances_frozen,
+                allow_zero_transfers: self.allow_zero_transfers,
+                mint_cooldown_secs: self.mint_cooldown_secs,
+                blacklist_action: self.blacklist_action,
+                large_transfer_threshold: self.large_transfer_threshold,
+                soulbound: self.soulbound,
+                prune_zero_balances: self.prune_zero_balances,
+                max_growth_bps_per_period: self.max_growth_bps_per_period,
+                growth_period_secs: self.growth_period_secs,
+                min_balance: self.min_balance,
+                dust_sweep: self.dust_sweep,
+                record_recent_transfers: self.record_recent_transfers,
+                min_transfer_amount: self.min_transfer_amount,
+                min_reserve_ratio_bps: self.min_reserve_ratio_bps,
+                reserve_price: self.reserve_price,
+                spender_daily_limit: self.spender_daily_limit,
+                hold_secs: self.hold_secs,
+                mint_allowlist_enabled: self.mint_allowlist_enabled,
+            }
+        }
+
+        /// Minimum spacing between owner mints; flash mints and staking rewards are
+        /// not counted.
+        #[ink(message)]
+        pub fn set_mint_cooldown_secs(&mut self, secs: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.mint_cooldown_secs = secs;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint_cooldown_secs(&self) -> u64 {
+            self.mint_cooldown_secs
+        }
+
+        #[ink(message)]
+        pub fn last_mint_at(&self) -> Option<Timestamp> {
+            self.last_mint_at
+        }
+
+        /// Caps owner-controlled mints (`mint`, `distribute_rewards`, staking rewards and
+        /// `bridge_mint`) per `period_secs` window at `bps` of the supply held when the
+        /// window opened; `bps == 0` removes the cap.
+        #[ink(message)]
+        pub fn set_growth_cap(&mut self, bps: u16, period_secs: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_growth_bps_per_period = bps;
+            self.growth_period_secs = period_secs;
+            self.growth_period_start = self.env().block_timestamp();
+            self.growth_period_base_supply = self.total_supply;
+            self.minted_this_period = 0;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn minted_this_period(&self) -> u128 {
+            self.minted_this_period
+        }
+
+        /// Share of the supply's native value (`total_supply * reserve_price`) the
+        /// contract must hold after a mint; 0 disables the check.
+        #[ink(message)]
+        pub fn set_min_reserve_ratio_bps(&mut self, bps: u16) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_reserve_ratio_bps = bps;
+            Ok(())
+        }
+
+        /// Owner-maintained oracle price: native units per token unit.
+        #[ink(message)]
+        pub fn set_reserve_price(&mut self, price: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.reserve_price = price;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn reserve_price(&self) -> Balance {
+            self.reserve_price
+        }
+
+        #[ink(message)]
+        pub fn set_max_wallet_amount(&mut self, amount: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_wallet_amount = amount;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_wallet_amount(&self) -> u128 {
+            self.max_wallet_amount
+        }
+
+        /// When enabled, `batch_transfer` emits one `BatchTransfer` summary instead of
+        /// a `Transfer` event per recipient.
+        #[ink(message)]
+        pub fn set_compact_batch_events(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.compact_batch_events = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn compact_batch_events(&self) -> bool {
+            self.compact_batch_events
+        }
+
+        #[ink(message)]
+        pub fn set_max_batch_size(&mut self, size: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_batch_size = size;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_batch_size(&self) -> u32 {
+            self.max_batch_size
+        }
+
+        /// Minimum time received tokens must sit before the receiver can send them on;
+        /// 0 disables the hold.
+        #[ink(message)]
+        pub fn set_hold_secs(&mut self, secs: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.hold_secs = secs;
+            Ok(())
+        }
+
+        /// When the last of `account`'s hold-period locks expires.
+        #[ink(message)]
+        pub fn locked_until(&self, account: AccountId) -> Timestamp {
+            self.hold_tranches
+                .get(account)
+                .unwrap_or_default()
+                .iter()
+                .map(|(_, until)| *until)
+                .max()
+                .unwrap_or(0)
+        }
+
+        /// Part of `account`'s balance still inside its hold period.
+        #[ink(message)]
+        pub fn locked_amount(&self, account: AccountId) -> u128 {
+            self.open_hold_tranches(account)
+                .iter()
+                .fold(0, |total, (amount, _)| total.saturating_add(*amount))
+        }
+
+        /// Smallest per-recipient amount `batch_transfer` accepts; 0 disables the check.
+        #[ink(message)]
+        pub fn set_min_transfer_amount(&mut self, amount: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_transfer_amount = amount;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_transfer_amount(&self) -> u128 {
+            self.min_transfer_amount
+        }
+
+        /// Checks every batch entry before any funds move, so a bad entry fails the
+        /// batch up front. A length mismatch is reported at the first unpaired index.
+        fn check_batch_entries(
+            &self,
+            from: AccountId,
+            recipients: &[AccountId],
+            amounts: &[u128],
+        ) -> core::result::Result<(), (u32, Error)> {
+            for (i, (recipient, amount)) in recipients.iter().zip(amounts).enumerate() {
+                let index = i as u32;
+                if *recipient == AccountId::from([0u8; 32]) {
+                    return Err((index, Error::ZeroAddress));
+                }
+                self.check_transfer_leg(from, *recipient, *amount)
+                    .map_err(|error| (index, error))?;
+            }
+            if recipients.len() != amounts.len() {
+                let index = recipients.len().min(amounts.len()) as u32;
+                return Err((index, Error::InvalidBatchOperation));
+            }
+            Ok(())
+        }
+
+        /// Runs the `transfer` checks and moves the balance without emitting an event.
+        fn move_tokens(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.check_transfer(from, to, amount)?;
+            if amount == 0 {
+                return Ok(());
+            }
+            self.apply_transfer(from, to, amount)
+        }
+
+        /// `move_tokens` for one leg of a multi-leg send, whose caller has checked
+        /// the sender's final leftover against `min_balance` up front.
+        fn move_tokens_leg(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.check_transfer_leg(from, to, amount)?;
+            if amount == 0 {
+                return Ok(());
+            }
+            self.apply_transfer(from, to, amount)
+        }
+
+        /// Returns the amount `transfer` should actually move: with dust sweeping on,
+        /// a leftover below `min_balance` is sent along too.
+        fn sweep_dust(&self, from: AccountId, amount: u128) -> u128 {
+            let remaining = self.balance_of(from).saturating_sub(amount);
+            if self.dust_sweep && remaining > 0 && remaining < self.min_balance {
+                return amount.saturating_add(remaining);
+            }
+            amount
+        }
+
+        /// A send may empty the sender or leave it at least `min_balance`, nothing
+        /// in between.
+        fn ensure_min_balance_left(&self, from: AccountId, amount: u128) -> Result<()> {
+            let remaining = self.balance_of(from).saturating_sub(amount);
+            if remaining > 0 && remaining < self.min_balance {
+                return Err(Error::BelowMinBalance);
+            }
+            Ok(())
+        }
+
+        /// Every `transfer` check, without touching state.
+        fn check_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.check_transfer_leg(from, to, amount)?;
+            self.ensure_min_balance_left(from, amount)
+        }
+
+        /// `transfer` checks minus `min_balance`, which multi-leg sends check once
+        /// against their total.
+        fn check_transfer_leg(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if amount > self.self_confirm_threshold(from) {
+                return Err(Error::SelfConfirmRequired);
+            }
+            self.check_confirmed_transfer(from, to, amount)
+        }
+
+        /// Per-leg `transfer` checks minus the sender's self-confirm threshold, for
+        /// transfers the sender has already confirmed.
+        fn check_confirmed_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.ensure_below_large_threshold(from, amount)?;
+            self.check_approvable_transfer(from, to, amount)
+        }
+
+        /// Per-leg `transfer` checks minus both thresholds: what a large transfer must
+        /// pass when it is queued and again when the owner approves it.
+        fn check_approvable_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.ensure_transfers_open(from)?;
+
+            if amount == 0 {
+                return self.zero_transfer_result();
+            }
+
+            self.ensure_hold_period_over(from, amount)?;
+            self.ensure_transfer_parties(from, to)?;
+
+            if self.balance_of(from) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.ensure_recipient_room(from, to, amount)
+        }
+
+        /// `check_approvable_transfer` for tokens `from` already put in escrow: their
+        /// balance and hold period were checked on the way in, the rest is checked
+        /// again on release.
+        fn check_escrow_release(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.ensure_transfers_open(from)?;
+            self.ensure_transfer_parties(from, to)?;
+            self.ensure_recipient_room(from, to, amount)
+        }
+
+        /// Global switches that stop `from` sending at all.
+        fn ensure_transfers_open(&self, from: AccountId) -> Result<()> {
+            if self.is_paused(PauseFlags::TRANSFERS) && !self.is_pause_exempt(from) {
+                return Err(Error::ContractPaused);
+            }
+
+            if !self.transfers_enabled {
+                return Err(Error::TransfersDisabled);
+            }
+
+            if self.soulbound {
+                return Err(Error::Soulbound);
+            }
+            Ok(())
+        }
+
+        /// Blacklist, whitelist and self-deposit rules on the two parties.
+        fn ensure_transfer_parties(&self, from: AccountId, to: AccountId) -> Result<()> {
+            if self.is_blacklist_blocked(from) || self.is_blacklisted(to) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            self.ensure_whitelisted(from, to)?;
+            self.ensure_not_self_deposit(to)
+        }
+
+        fn ensure_recipient_room(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if from != to {
+                let new_to_balance = self
+                    .balance_of(to)
+                    .checked_add(amount)
+                    .ok_or(Error::Overflow)?;
+                self.ensure_within_max_wallet(to, new_to_balance)?;
+            }
+            Ok(())
+        }
+
+        /// When a loosening of `account`'s self-confirm settings requested now takes
+        /// effect: once its current delay has passed.
+        fn self_confirm_change_at(&self, account: AccountId) -> Timestamp {
+            self.env()
+                .block_timestamp()
+                .saturating_add(self.self_confirm_delay(account).saturating_mul(1000))
+        }
+
+        /// A queued self-transfer, provided it belongs to the caller.
+        fn own_self_transfer(&self, id: u64) -> Result<(TransferRecord, Timestamp)> {
+            let caller = self.env().caller();
+            match self.pending_self_transfers.get(id) {
+                Some(entry @ ((from, _, _), _)) if from == caller => Ok(entry),
+                _ => Err(Error::UnknownSelfTransfer),
+            }
+        }
+
+        /// Switches that gate every allowance-based transfer made by `spender`.
+        fn ensure_allowance_spending_open(&self, spender: AccountId) -> Result<()> {
+            if self.is_paused(PauseFlags::TRANSFERS) && !self.is_pause_exempt(spender) {
+                return Err(Error::ContractPaused);
+            }
+
+            if !self.transfers_enabled {
+                return Err(Error::TransfersDisabled);
+            }
+
+            if self.soulbound {
+                return Err(Error::Soulbound);
+            }
+
+            if self.allowances_frozen {
+                return Err(Error::AllowancesFrozen);
+            }
+            Ok(())
+        }
+
+        /// Charges `amount` against the spender's daily window, opening a new window
+        /// once the previous one is a day old. `DAILY_LIMIT`-exempt spenders skip it.
+        fn record_spender_volume(&mut self, spender: AccountId, amount: u128) -> Result<()> {
+            if self.spender_daily_limit == u128::MAX
+                || self.is_exempt(spender, ExemptionFlags::DAILY_LIMIT)
+            {
+                return Ok(());
+            }
+
+            let now = self.env().block_timestamp();
+            let (start, spent) = match self.spender_daily_spent.get(spender) {
+                Some((start, spent)) if now < start.saturating_add(DAY_MS) => (start, spent),
+                _ => (now, 0),
+            };
+
+            let spent = spent.saturating_add(amount);
+            if spent > self.spender_daily_limit {
+                return Err(Error::DailyLimitExceeded);
+            }
+            self.spender_daily_spent.insert(spender, &(start, spent));
+            Ok(())
+        }
+
+        /// Per-leg checks and balance move for allowance-based transfers. Unlike
+        /// `transfer`, a blacklisted `from` gets no grace window here.
+        fn move_tokens_from(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if amount == 0 {
+                return self.zero_transfer_result();
+            }
+
+            if amount > self.self_confirm_threshold(from) {
+                return Err(Error::SelfConfirmRequired);
+            }
+
+            self.ensure_below_large_threshold(from, amount)?;
+            self.ensure_hold_period_over(from, amount)?;
+
+            if self.is_blacklisted(from) || self.is_blacklisted(to) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            self.ensure_whitelisted(from, to)?;
+            self.ensure_not_self_deposit(to)?;
+
+            if self.balance_of(from) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.apply_transfer(from, to, amount)
+        }
+
+        /// Amounts above the large-transfer threshold must go through
+        /// `queue_large_transfer`; the owner and `LARGE_TRANSFER`-exempt senders are exempt.
+        fn ensure_below_large_threshold(&self, from: AccountId, amount: u128) -> Result<()> {
+            if from != self.owner
+                && !self.is_exempt(from, ExemptionFlags::LARGE_TRANSFER)
+                && amount > self.large_transfer_threshold
+            {
+                return Err(Error::LargeTransferRequiresApproval);
+            }
+            Ok(())
+        }
+
+        /// Tokens received within the last `hold_secs` can't be sent on; the rest of
+        /// the balance stays spendable. `HOLD_PERIOD`-exempt accounts skip the wait,
+        /// and an amount beyond the whole balance is left to the balance check.
+        fn ensure_hold_period_over(&self, from: AccountId, amount: u128) -> Result<()> {
+            if self.is_exempt(from, ExemptionFlags::HOLD_PERIOD) {
+                return Ok(());
+            }
+            let balance = self.balance_of(from);
+            let spendable = balance.saturating_sub(self.locked_amount(from));
+            if amount > spendable && amount <= balance {
+                return Err(Error::HoldPeriodActive);
+            }
+            Ok(())
+        }
+
+        /// Locks the `amount` just received by `account` until its own expiry, so a
+        /// later receipt never pushes back an earlier one's. Once `MAX_HOLD_TRANCHES`
+        /// locks are open, further receipts join the newest one and it takes their
+        /// expiry; the older locks still run out on time, so that caps any delay a
+        /// third party can add at one extra hold period.
+        fn start_hold_period(&mut self, account: AccountId, amount: u128) {
+            if self.hold_secs == 0 {
+                return;
+            }
+            let until = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.hold_secs.saturating_mul(1000));
+            let mut tranches = self.open_hold_tranches(account);
+            let full = tranches.len() >= MAX_HOLD_TRANCHES;
+            match tranches.last_mut() {
+                Some((locked, last_until)) if full || *last_until == until => {
+                    *locked = locked.saturating_add(amount);
+                    *last_until = until;
+                }
+                _ => tranches.push((amount, until)),
+            }
+            self.hold_tranches.insert(account, &tranches);
+        }
+
+        /// `account`'s hold-period locks that haven't expired yet, oldest first.
+        fn open_hold_tranches(&self, account: AccountId) -> Vec<HoldTranche> {
+            let now = self.env().block_timestamp();
+            let mut tranches = self.hold_tranches.get(account).unwrap_or_default();
+            tranches.retain(|(_, until)| *until > now);
+            tranches
+        }
+
+        /// Zero-value transfers are rejected unless the ERC20-style no-op mode is on,
+        /// in which case the caller still emits a zero `Transfer`.
+        fn zero_transfer_result(&self) -> Result<()> {
+            if self.allow_zero_transfers {
+                Ok(())
+            } else {
+                Err(Error::ZeroAmount)
+            }
+        }
+
+        /// Moves an already-validated `amount` with checked math, so a desynced balance
+        /// surfaces as an error instead of being silently clamped.
+        fn apply_transfer(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if from == to {
+                return Ok(());
+            }
+
+            let new_from_balance = self
+                .balance_of(from)
+                .checked_sub(amount)
+                .ok_or(Error::SupplyInvariantBroken)?;
+            let new_to_balance = self
+                .balance_of(to)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.ensure_within_max_wallet(to, new_to_balance)?;
+
+            self.set_balance(from, new_from_balance);
+            self.set_balance(to, new_to_balance);
+            self.start_hold_period(to, amount);
+            Ok(())
+        }
+
+        /// Moves `amount` from `from` into the contract's escrow balance.
+        fn escrow_in(&mut self, from: AccountId, amount: u128) -> Result<()> {
+            let contract = self.env().account_id();
+            self.transfer_silent(from, contract, amount)?;
+            self.total_escrowed = self.total_escrowed.checked_add(amount).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        /// Releases `amount` of escrowed tokens from the contract to `to`.
+        fn escrow_out(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            let contract = self.env().account_id();
+            self.transfer_silent(contract, to, amount)?;
+            self.total_escrowed = self
+                .total_escrowed
+                .checked_sub(amount)
+                .ok_or(Error::SupplyInvariantBroken)?;
+            Ok(())
+        }
+
+        /// Balance move for privileged internal flows such as staking escrow: no
+        /// `Transfer` event and no max-wallet cap, since those flows emit their own
+        /// domain events (`Staked`, `Unstaked`). External messages never route here.
+        fn transfer_silent(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if from == to {
+                return Ok(());
+            }
+
+            let new_from_balance = self
+                .balance_of(from)
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientBalance)?;
+            let new_to_balance = self
+                .balance_of(to)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+
+            self.set_balance(from, new_from_balance);
+            self.set_balance(to, new_to_balance);
+            Ok(())
+        }
+
+        /// `acc_reward_per_share` as it would be after an `update_pool` at this block.
+        fn current_acc_reward_per_share(&self) -> u128 {
+            let now = self.env().block_number();
+            if now <= self.last_reward_block || self.total_staked == 0 {
+                return self.acc_reward_per_share;
+            }
+            let blocks = u128::from(now - self.last_reward_block);
+            let reward = blocks.saturating_mul(self.reward_per_block);
+            self.acc_reward_per_share
+                .saturating_add(reward.saturating_mul(ACC_REWARD_PRECISION) / self.total_staked)
+        }
+
+        fn update_pool(&mut self) {
+            self.acc_reward_per_share = self.current_acc_reward_per_share();
+            self.last_reward_block = self.env().block_number();
+        }
+
+        /// Mints `account`'s pending reward; the pool must be updated beforehand and
+        /// the reward debt reset afterwards. Whatever the growth cap or the native
+        /// reserve doesn't leave room for is deferred to a later harvest rather than
+        /// blocking the caller.
+        fn harvest(&mut self, account: AccountId) -> Result<u128> {
+            let pending = self.pending_reward(account);
+            let now = self.env().block_timestamp();
+            let reward = pending
+                .min(self.growth_headroom(now)?)
+                .min(self.reserve_headroom());
+            if reward > 0 {
+                self.record_period_growth(now, reward)?;
+                self.credit_minted(account, reward);
+                self.env().emit_event(RewardClaimed { account, amount: reward });
+            }
+            let deferred = pending.saturating_sub(reward);
+            if deferred > 0 {
+                self.deferred_rewards.insert(account, &deferred);
+            } else {
+                self.deferred_rewards.remove(account);
+            }
+            Ok(reward)
+        }
+
+        /// Credits freshly minted tokens and bumps supply; callers do their own checks.
+        fn credit_minted(&mut self, to: AccountId, amount: u128) {
+            let balance = self.balance_of(to);
+            self.set_balance(to, balance.saturating_add(amount));
+            self.total_supply = self.total_supply.saturating_add(amount);
+            self.emit_minted(to, amount);
+        }
+
+        /// Owner mints must be `mint_cooldown_secs` apart.
+        fn ensure_mint_cooldown_over(&self, now: Timestamp) -> Result<()> {
+            if let Some(last) = self.last_mint_at {
+                if now < last.saturating_add(self.mint_cooldown_secs.saturating_mul(1000)) {
+                    return Err(Error::MintCooldownActive);
+                }
+            }
+            Ok(())
+        }
+
+        /// Recipient checks shared by the mint paths: blacklist, transfer whitelist,
+        /// mint allowlist and the max-wallet cap on the credited balance.
+        fn ensure_mint_recipient(&self, to: AccountId, amount: u128) -> Result<()> {
+            if self.is_blacklisted(to) {
+                return Err(Error::AccountBlacklisted);
+            }
+
+            if self.whitelist_mode && !self.is_whitelisted(to) {
+                return Err(Error::NotWhitelisted);
+            }
+
+            if self.mint_allowlist_enabled && !self.is_mint_allowlisted(to) {
+                return Err(Error::NotWhitelisted);
+            }
+
+            let new_balance = self.balance_of(to).checked_add(amount).ok_or(Error::Overflow)?;
+            self.ensure_within_max_wallet(to, new_balance)
+        }
+
+        fn reset_reward_debt(&mut self, account: AccountId, staked: u128) {
+            let debt = staked.saturating_mul(self.acc_reward_per_share) / ACC_REWARD_PRECISION;
+            self.reward_debt.insert(account, &debt);
+        }
+
+        /// Writes the root storage struct out ahead of a reentrant call; ink! otherwise
+        /// only persists it when the message returns.
+        fn flush_state(&self) {
+            ink::env::set_contract_storage(&<Self as ink::storage::traits::StorageKey>::KEY, self);
+        }
+
+        /// Reloads the root storage struct after a reentrant call, picking up whatever
+        /// the nested calls wrote.
+        fn reload_state(&mut self) {
+            let key = <Self as ink::storage::traits::StorageKey>::KEY;
+            if let Ok(Some(state)) = ink::env::get_contract_storage::<_, Self>(&key) {
+                *self = state;
+            }
+        }
+
+        fn is_paused(&self, flag: u8) -> bool {
+            self.pause_flags & flag != 0
+        }
+
+        /// Gate for owner-only calls; also records the call for the dead-man switch.
+        fn ensure_owner(&mut self) -> Result<()> {
+            if !self.is_owned() {
+                return Err(Error::OwnershipRenounced);
+            }
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.last_owner_action = self.env().block_timestamp();
+            Ok(())
+        }
+
+        /// Balance-moving events carry a gap-free sequence number so indexers can
+        /// detect missed events.
+        fn next_event_seq(&mut self) -> u64 {
+            self.event_seq = self.event_seq.saturating_add(1);
+            self.event_seq
+        }
+
+        fn emit_transfer(&mut self, from: AccountId, to: AccountId, amount: u128) {
+            let seq = self.next_event_seq();
+            self.env().emit_event(Transfer { from, to, amount, seq });
+            self.record_recent_transfer(from, to, amount);
+        }
+
+        fn record_recent_transfer(&mut self, from: AccountId, to: AccountId, amount: u128) {
+            if !self.record_recent_transfers {
+                return;
+            }
+            let now = self.env().block_timestamp();
+            self.recent_transfers
+                .insert(self.recent_transfers_next, &(from, to, amount, now));
+            self.recent_transfers_next = (self.recent_transfers_next + 1) % RECENT_TRANSFERS_CAPACITY;
+            if self.recent_transfers_len < RECENT_TRANSFERS_CAPACITY {
+                self.recent_transfers_len += 1;
+            }
+        }
+
+        fn emit_minted(&mut self, to: AccountId, amount: u128) {
+            let seq = self.next_event_seq();
+            self.env().emit_event(Minted { to, amount, seq });
+        }
+
+        fn emit_burned(&mut self, from: AccountId, amount: u128) {
+            let seq = self.next_event_seq();
+            self.env().emit_event(Burned { from, amount, seq });
+        }
+
+        /// Writes a balance and keeps `holder_count` in step. With pruning on, an entry
+        /// that hits zero is removed so the storage deposit is reclaimed; otherwise a
+        /// zero is written, which is cheaper on hot paths.
+        fn set_balance(&mut self, account: AccountId, balance: u128) {
+            let previous = self.balances.get(account);
+            let was_holder = previous.unwrap_or(0) > 0;
+            if was_holder && balance == 0 {
+                self.holder_count = self.holder_count.saturating_sub(1);
+            } else if !was_holder && balance > 0 {
+                self.holder_count = self.holder_count.saturating_add(1);
+            }
+
+            if balance == 0 && self.prune_zero_balances {
+                if previous.is_some() {
+                    self.balances.remove(account);
+                }
+            } else {
+                self.balances.insert(account, &balance);
+            }
+        }
+
+        /// Charges `amount` against an allowance the caller already checked. Infinite
+        /// (`u128::MAX`) allowances are left as they are, as in most ERC-20s.
+        fn spend_allowance(&mut self, owner: AccountId, spender: AccountId, allowance: u128, amount: u128) {
+            if allowance != u128::MAX {
+                self.set_allowance(owner, spender, allowance.saturating_sub(amount));
+            }
+        }
+
+        /// Writes an allowance and keeps both per-account totals in step: finite
+        /// amounts go into the running sums, infinite (`u128::MAX`) ones into counts.
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, amount: u128) {
+            let previous = self.allowance(owner, spender);
+            self.allowances.insert((owner, spender), &amount);
+
+            let finite = |allowance: u128| if allowance == u128::MAX { 0 } else { allowance };
+
+            let total = self
+                .total_allowance_granted
+                .get(owner)
+                .unwrap_or(0)
+                .saturating_sub(finite(previous))
+                .saturating_add(finite(amount));
+            self.total_allowance_granted.insert(owner, &total);
+
+            let received = self
+                .allowance_received
+                .get(spender)
+                .unwrap_or(0)
+                .saturating_sub(finite(previous))
+                .saturating_add(finite(amount));
+            self.allowance_received.insert(spender, &received);
+
+            if (previous == u128::MAX) != (amount == u128::MAX) {
+                let step = |count: u32| {
+                    if amount == u128::MAX {
+                        count.saturating_add(1)
+                    } else {
+                        count.saturating_sub(1)
+                    }
+                };
+                let granted = step(self.infinite_allowances_granted.get(owner).unwrap_or(0));
+                self.infinite_allowances_granted.insert(owner, &granted);
+                let received = step(self.infinite_allowances_received.get(spender).unwrap_or(0));
+                self.infinite_allowances_received.insert(spender, &received);
+            }
+        }
+
+        /// Re-blacklisting an already blacklisted account keeps its original timestamp,
+        /// so the grace window isn't reopened.
+        fn set_blacklist_status(&mut self, account: AccountId, status: bool) {
+            if status {
+                if !self.is_blacklisted(account) {
+                    self.blacklisted_at.insert(account, &self.env().block_timestamp());
+                }
+            } else {
+                self.blacklisted_at.remove(account);
+            }
+            self.blacklist.insert(account, &status);
+            self.env().emit_event(Blacklisted { account, status });
+        }
+
+        /// A freshly blacklisted account may keep sending via `transfer` until its
+        /// grace window (measured from the blacklist timestamp) has elapsed.
+        fn is_blacklist_blocked(&self, account: AccountId) -> bool {
+            if !self.is_blacklisted(account) {
+                return false;
+            }
+            let since = self.blacklisted_at.get(account).unwrap_or(0);
+            let grace_ms = self.blacklist_grace_secs.saturating_mul(1000);
+            self.env().block_timestamp() >= since.saturating_add(grace_ms)
+        }
+
+        /// The owner and the contract's own account can never be blacklisted, so
+        /// owner-held supply and contract-held funds can't be frozen by mistake.
+        fn ensure_blacklistable(&self, account: AccountId, status: bool) -> Result<()> {
+            if status && (account == self.owner || account == self.env().account_id()) {
+                return Err(Error::CannotBlacklistProtected);
+            }
+            Ok(())
+        }
+
+        /// Best-effort `on_blacklist` callback: a failing hook is reported through
+        /// `HookFailed` and never reverts the blacklist change.
+        fn notify_compliance_hook(&mut self, account: AccountId, status: bool) {
+            let Some(hook) = self.compliance_hook else {
+                return;
+            };
+            let selector = ink::selector_bytes!("on_blacklist");
+            let result = build_call::<Environment>()
+                .call(hook)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(selector))
+                        .push_arg(account)
+                        .push_arg(status),
+                )
+                .returns::<()>()
+                .try_invoke();
+            if !matches!(result, Ok(Ok(()))) {
+                self.env().emit_event(HookFailed { hook, selector });
+            }
+        }
+
+        /// In whitelist mode both sides of a transfer must be whitelisted.
+        fn ensure_whitelisted(&self, from: AccountId, to: AccountId) -> Result<()> {
+            if self.whitelist_mode && (!self.is_whitelisted(from) || !self.is_whitelisted(to)) {
+                return Err(Error::NotWhitelisted);
+            }
+            Ok(())
+        }
+
+        fn ensure_not_self_deposit(&self, to: AccountId) -> Result<()> {
+            if self.block_self_deposit && to == self.env().account_id() {
+                return Err(Error::SelfDepositBlocked);
+            }
+            Ok(())
+        }
+
+        /// Native balance the outstanding supply has a claim on: its value at
+        /// `reserve_price`, or the reserve ratio's share of that if higher. With no
+        /// price set the whole balance backs `rage_quit` while any supply exists.
+        fn required_native_backing(&self) -> Result<Balance> {
+            if self.total_supply == 0 {
+                return Ok(0);
+            }
+            if self.reserve_price == 0 {
+                return Ok(self.env().balance());
+            }
+            let ratio = u128::from(self.min_reserve_ratio_bps).max(10_000);
+            let unit_backing = self.reserve_price.checked_mul(ratio).ok_or(Error::Overflow)?;
+            mul_div(self.total_supply, unit_backing, 10_000).ok_or(Error::Overflow)
+        }
+
+        /// Rejects a mint whose post-mint supply value, scaled by the reserve ratio,
+        /// would exceed the contract's native balance.
+        fn ensure_reserves_cover(&self, amount: u128) -> Result<()> {
+            if amount > self.reserve_headroom() {
+                return Err(Error::InsufficientReserves);
+            }
+            Ok(())
+        }
+
+        /// Most that can still be minted before `total_supply * reserve_price`, scaled
+        /// by `min_reserve_ratio_bps`, outgrows the contract's native balance.
+        fn reserve_headroom(&self) -> u128 {
+            if self.min_reserve_ratio_bps == 0 || self.reserve_price == 0 {
+                return u128::MAX;
+            }
+            let Some(unit_backing) = self
+                .reserve_price
+                .checked_mul(u128::from(self.min_reserve_ratio_bps))
+            else {
+                return 0;
+            };
+            mul_div(self.env().balance(), 10_000, unit_backing)
+                .unwrap_or(u128::MAX)
+                .saturating_sub(self.total_supply)
+        }
+
+        /// Rolls the growth window forward when it has elapsed, then returns how much
+        /// may still be minted in it. A window opened on an empty supply has nothing to
+        /// grow from, so it is uncapped.
+        fn growth_headroom(&mut self, now: Timestamp) -> Result<u128> {
+            if self.max_growth_bps_per_period == 0 {
+                return Ok(u128::MAX);
+            }
+
+            let period_ms = self.growth_period_secs.saturating_mul(1000);
+            if now >= self.growth_period_start.saturating_add(period_ms) {
+                self.growth_period_start = now;
+                self.growth_period_base_supply = self.total_supply;
+                self.minted_this_period = 0;
+            }
+
+            if self.growth_period_base_supply == 0 {
+                return Ok(u128::MAX);
+            }
+
+            let cap = self
+                .growth_period_base_supply
+                .checked_mul(u128::from(self.max_growth_bps_per_period))
+                .ok_or(Error::Overflow)?
+                / 10_000;
+            Ok(cap.saturating_sub(self.minted_this_period))
+        }
+
+        /// Charges `amount` against the period cap. The first mint into an empty
+        /// supply reopens the window on the supply it creates, so the cap bites from
+        /// the next mint on.
+        fn record_period_growth(&mut self, now: Timestamp, amount: u128) -> Result<()> {
+            if amount > self.growth_headroom(now)? {
+                return Err(Error::GrowthCapExceeded);
+            }
+            if self.max_growth_bps_per_period == 0 {
+                return Ok(());
+            }
+
+            if self.growth_period_base_supply == 0 {
+                self.growth_period_start = now;
+                self.growth_period_base_supply = self.total_supply.saturating_add(amount);
+                self.minted_this_period = 0;
+            } else {
+                self.minted_this_period = self.minted_this_period.saturating_add(amount);
+            }
+            Ok(())
+        }
+
+        /// Rejects a recipient balance above the max wallet cap; the owner and
+        /// `MAX_WALLET`-exempt accounts are exempt.
+        fn ensure_within_max_wallet(&self, account: AccountId, new_balance: u128) -> Result<()> {
+            if account != self.owner
+                && !self.is_exempt(account, ExemptionFlags::MAX_WALLET)
+                && new_balance > self.max_wallet_amount
+            {
+                return Err(Error::ExceedsMaxWallet);
+            }
+            Ok(())
+        }
+
+        fn is_exempt(&self, account: AccountId, flag: u8) -> bool {
+            self.exemption_flags(account) & flag != 0
+        }
+
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test, DefaultEnvironment};
+
+        /// Account the contract under test is deployed at.
+        const CONTRACT: [u8; 32] = [0xC0; 32];
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        /// Deploys with alice as owner at `CONTRACT`, leaving alice as the caller.
+        fn deploy() -> InkyBank {
+            test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+            set_caller(accounts().alice);
+            InkyBank::new()
+        }
+
+        /// Deploys and mints `amount` to alice.
+        fn deploy_funded(amount: u128) -> InkyBank {
+            let mut bank = deploy();
+            bank.mint(accounts().alice, amount).unwrap();
+            bank
+        }
+
+        #[ink::test]
+        fn max_wallet_fills_to_cap_then_rejects_excess() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_max_wallet_amount(100).unwrap();
+
+            bank.transfer(accounts.bob, 60).unwrap();
+            bank.mint(accounts.bob, 40).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 100);
+
+            assert_eq!(bank.transfer(accounts.bob, 1), Err(Error::ExceedsMaxWallet));
+            assert_eq!(bank.mint(accounts.bob, 1), Err(Error::ExceedsMaxWallet));
+
+            bank.approve(accounts.charlie, 1).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 1),
+                Err(Error::ExceedsMaxWallet)
+            );
+            assert_eq!(bank.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn max_wallet_skips_owner_and_exempt_accounts() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_max_wallet_amount(100).unwrap();
+            bank.set_exemption_flags(accounts.bob, ExemptionFlags::MAX_WALLET).unwrap();
+
+            bank.transfer(accounts.bob, 500).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 500);
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.alice, 500).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+        }
+
+        #[ink::test]
+        fn migrate_account_moves_balance_and_stake() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.stake(400).unwrap();
+
+            bank.migrate_account(accounts.bob).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 0);
+            assert_eq!(bank.staked_balance(accounts.alice), 0);
+            assert_eq!(bank.balance_of(accounts.bob), 600);
+            assert_eq!(bank.staked_balance(accounts.bob), 400);
+        }
+
+        #[ink::test]
+        fn migrate_account_runs_transfer_checks() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_max_wallet_amount(450).unwrap();
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.charlie, 200).unwrap();
+            assert_eq!(bank.migrate_account(accounts.charlie), Err(Error::ExceedsMaxWallet));
+
+            set_caller(accounts.alice);
+            bank.set_whitelist_mode(true).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.migrate_account(accounts.django), Err(Error::NotWhitelisted));
+
+            set_caller(accounts.alice);
+            bank.set_whitelist_mode(false).unwrap();
+            bank.set_transfers_enabled(false).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.migrate_account(accounts.django), Err(Error::TransfersDisabled));
+            assert_eq!(bank.balance_of(accounts.bob), 300);
+        }
+
+        #[ink::test]
+        fn hold_period_locks_only_received_tokens() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_hold_secs(60).unwrap();
+            bank.transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.locked_amount(accounts.bob), 100);
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer(accounts.charlie, 501), Err(Error::HoldPeriodActive));
+            bank.transfer(accounts.charlie, 500).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            assert_eq!(bank.locked_amount(accounts.bob), 0);
+            bank.transfer(accounts.charlie, 100).unwrap();
+        }
+
+        #[ink::test]
+        fn hold_period_dust_sends_cannot_freeze_existing_balance() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.transfer(accounts.eve, 10).unwrap();
+            bank.set_hold_secs(60).unwrap();
+
+            for step in 1..=5u64 {
+                test::set_block_timestamp::<DefaultEnvironment>(step * 30_000);
+                set_caller(accounts.eve);
+                bank.transfer(accounts.bob, 1).unwrap();
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.locked_amount(accounts.bob), 2);
+            bank.transfer(accounts.charlie, 500).unwrap();
+            assert_eq!(bank.transfer(accounts.charlie, 4), Err(Error::HoldPeriodActive));
+        }
+
+        #[ink::test]
+        fn self_confirm_threshold_applies_to_allowance_spends() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_self_confirm_threshold(100).unwrap();
+            bank.approve(accounts.bob, 500).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.charlie, 101),
+                Err(Error::SelfConfirmRequired)
+            );
+            assert_eq!(
+                bank.distribute_from(accounts.alice, vec![accounts.charlie], vec![101]),
+                Err(Error::SelfConfirmRequired)
+            );
+            bank.transfer_from(accounts.alice, accounts.charlie, 100).unwrap();
+        }
+
+        #[ink::test]
+        fn self_confirm_loosening_waits_out_current_delay() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_self_confirm_delay(60).unwrap();
+            bank.set_self_confirm_threshold(100).unwrap();
+            assert_eq!(bank.self_confirm_threshold(accounts.alice), 100);
+
+            bank.set_self_confirm_threshold(u128::MAX).unwrap();
+            bank.set_self_confirm_delay(0).unwrap();
+            assert_eq!(bank.self_confirm_threshold(accounts.alice), 100);
+            assert_eq!(bank.self_confirm_delay(accounts.alice), 60);
+            assert_eq!(bank.transfer(accounts.bob, 101), Err(Error::SelfConfirmRequired));
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            assert_eq!(bank.self_confirm_threshold(accounts.alice), u128::MAX);
+            assert_eq!(bank.self_confirm_delay(accounts.alice), 0);
+            bank.transfer(accounts.bob, 101).unwrap();
+        }
+
+        #[ink::test]
+        fn self_confirm_tightening_applies_at_once() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_self_confirm_delay(60).unwrap();
+            bank.set_self_confirm_threshold(100).unwrap();
+            bank.set_self_confirm_threshold(u128::MAX).unwrap();
+            bank.set_self_confirm_threshold(50).unwrap();
+            assert_eq!(bank.self_confirm_threshold(accounts.alice), 50);
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            assert_eq!(bank.self_confirm_threshold(accounts.alice), 50);
+            assert_eq!(bank.transfer(accounts.bob, 51), Err(Error::SelfConfirmRequired));
+        }
+
+        #[ink::test]
+        fn subscription_pulls_once_per_period() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            assert_eq!(bank.create_subscription(accounts.bob, 10, 0), Err(Error::ZeroPeriod));
+            bank.create_subscription(accounts.bob, 10, 60).unwrap();
+
+            set_caller(accounts.bob);
+            bank.pull_subscription(accounts.alice).unwrap();
+            assert_eq!(bank.pull_subscription(accounts.alice), Err(Error::SubscriptionNotDue));
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            bank.pull_subscription(accounts.alice).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 20);
+        }
+
+        #[ink::test]
+        fn subscription_pulls_respect_allowance_switches() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.create_subscription(accounts.bob, 10, 60).unwrap();
+            bank.set_allowances_frozen(true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.pull_subscription(accounts.alice), Err(Error::AllowancesFrozen));
+
+            set_caller(accounts.alice);
+            bank.set_allowances_frozen(false).unwrap();
+            bank.set_spender_daily_limit(5).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.pull_subscription(accounts.alice), Err(Error::DailyLimitExceeded));
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn flash_mint_applies_mint_recipient_checks() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            assert_eq!(
+                bank.flash_mint(accounts.bob, 100, Vec::new()),
+                Err(Error::AccountBlacklisted)
+            );
+
+            bank.set_mint_allowlist_enabled(true).unwrap();
+            assert_eq!(
+                bank.flash_mint(accounts.charlie, 100, Vec::new()),
+                Err(Error::NotWhitelisted)
+            );
+            bank.set_mint_allowlisted(accounts.charlie, true).unwrap();
+
+            bank.set_max_wallet_amount(50).unwrap();
+            assert_eq!(
+                bank.flash_mint(accounts.charlie, 100, Vec::new()),
+                Err(Error::ExceedsMaxWallet)
+            );
+
+            bank.set_max_wallet_amount(u128::MAX).unwrap();
+            bank.set_reserve_price(1_000_000).unwrap();
+            bank.set_min_reserve_ratio_bps(10_000).unwrap();
+            test::set_account_balance::<DefaultEnvironment>(AccountId::from(CONTRACT), 1_000_000);
+            assert_eq!(
+                bank.flash_mint(accounts.charlie, 100, Vec::new()),
+                Err(Error::InsufficientReserves)
+            );
+            assert_eq!(bank.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn total_allowance_granted_sums_every_spender() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 100).unwrap();
+            bank.approve(accounts.charlie, 50).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 150);
+
+            bank.approve(accounts.bob, 30).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 80);
+
+            set_caller(accounts.charlie);
+            bank.transfer_from(accounts.alice, accounts.django, 20).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 60);
+            assert_eq!(bank.total_allowance_received(accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn total_allowance_granted_recovers_after_infinite_revoked() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 100).unwrap();
+            bank.approve(accounts.charlie, u128::MAX).unwrap();
+            bank.approve(accounts.django, u128::MAX).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), u128::MAX);
+
+            bank.approve(accounts.django, 0).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), u128::MAX);
+
+            bank.approve(accounts.charlie, 5).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 105);
+            assert_eq!(bank.total_allowance_received(accounts.charlie), 5);
+        }
+
+        #[ink::test]
+        fn staking_rejects_blacklisted_accounts() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_reward_per_block(10).unwrap();
+
+            set_caller(accounts.bob);
+            bank.stake(200).unwrap();
+            test::advance_block::<DefaultEnvironment>();
+
+            set_caller(accounts.alice);
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.stake(100), Err(Error::AccountBlacklisted));
+            assert_eq!(bank.claim_rewards(), Err(Error::AccountBlacklisted));
+        }
+
+        #[ink::test]
+        fn seize_takes_balance_and_stake() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            set_caller(accounts.bob);
+            bank.stake(200).unwrap();
+
+            set_caller(accounts.alice);
+            bank.set_blacklist_action(BlacklistAction::Seize).unwrap();
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            bank.seize(accounts.bob, accounts.charlie).unwrap();
+
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+            assert_eq!(bank.staked_balance(accounts.bob), 0);
+            assert_eq!(bank.total_staked(), 0);
+            assert_eq!(bank.total_escrowed(), 0);
+            assert_eq!(bank.balance_of(accounts.charlie), 500);
+        }
+
+        #[ink::test]
+        fn distribute_rewards_splits_pro_rata() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(300);
+            bank.transfer(accounts.bob, 100).unwrap();
+
+            bank.distribute_rewards(vec![accounts.alice, accounts.bob], 31).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 221);
+            assert_eq!(bank.balance_of(accounts.bob), 110);
+            assert_eq!(bank.total_supply(), 331);
+        }
+
+        #[ink::test]
+        fn distribute_rewards_applies_mint_checks() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(300);
+            bank.transfer(accounts.bob, 100).unwrap();
+            let holders = vec![accounts.alice, accounts.bob];
+
+            bank.set_max_wallet_amount(105).unwrap();
+            assert_eq!(bank.distribute_rewards(holders.clone(), 30), Err(Error::ExceedsMaxWallet));
+            bank.set_max_wallet_amount(u128::MAX).unwrap();
+
+            bank.set_mint_allowlist_enabled(true).unwrap();
+            bank.set_mint_allowlisted(accounts.alice, true).unwrap();
+            assert_eq!(bank.distribute_rewards(holders.clone(), 30), Err(Error::NotWhitelisted));
+            bank.set_mint_allowlist_enabled(false).unwrap();
+
+            bank.set_mint_cooldown_secs(60).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            bank.distribute_rewards(holders.clone(), 30).unwrap();
+            assert_eq!(bank.distribute_rewards(holders.clone(), 30), Err(Error::MintCooldownActive));
+            assert_eq!(bank.mint(accounts.alice, 1), Err(Error::MintCooldownActive));
+
+            test::set_block_timestamp::<DefaultEnvironment>(120_000);
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            assert_eq!(bank.distribute_rewards(holders, 30), Err(Error::AccountBlacklisted));
+            assert_eq!(bank.total_supply(), 330);
+        }
+
+        #[ink::test]
+        fn growth_cap_set_on_empty_supply_allows_bootstrap_mint() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_growth_cap(1_000, 60).unwrap();
+
+            bank.mint(accounts.alice, 1_000).unwrap();
+            bank.mint(accounts.alice, 100).unwrap();
+            assert_eq!(bank.mint(accounts.alice, 1), Err(Error::GrowthCapExceeded));
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            bank.mint(accounts.alice, 110).unwrap();
+        }
+
+        #[ink::test]
+        fn growth_cap_covers_every_owner_mint_path() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_growth_cap(1_000, 60).unwrap();
+            bank.set_bridge_relayer(Some(accounts.django)).unwrap();
+
+            assert_eq!(
+                bank.distribute_rewards(vec![accounts.alice], 101),
+                Err(Error::GrowthCapExceeded)
+            );
+            bank.distribute_rewards(vec![accounts.alice], 60).unwrap();
+
+            set_caller(accounts.django);
+            assert_eq!(
+                bank.bridge_mint(accounts.bob, 41, 7, 0),
+                Err(Error::GrowthCapExceeded)
+            );
+            bank.bridge_mint(accounts.bob, 40, 7, 0).unwrap();
+            assert_eq!(bank.minted_this_period(), 100);
+        }
+
+        #[ink::test]
+        fn growth_cap_defers_staking_rewards_it_cannot_cover() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.stake(1_000).unwrap();
+            bank.set_reward_per_block(150).unwrap();
+            bank.set_growth_cap(1_000, 60).unwrap();
+            test::advance_block::<DefaultEnvironment>();
+
+            assert_eq!(bank.pending_reward(accounts.alice), 150);
+            bank.claim_rewards().unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 100);
+            assert_eq!(bank.pending_reward(accounts.alice), 50);
+
+            bank.unstake(1_000).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_100);
+            assert_eq!(bank.pending_reward(accounts.alice), 50);
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            bank.claim_rewards().unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_150);
+        }
+
+        #[ink::test]
+        fn mul_div_handles_products_above_u128() {
+            assert_eq!(mul_div(6, 7, 3), Some(14));
+            assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+            assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+            assert_eq!(mul_div(u128::MAX, 2, 1), None);
+            assert_eq!(mul_div(1, 1, 0), None);
+        }
+
+        #[ink::test]
+        fn rage_quit_pays_pro_rata_share_at_large_sizes() {
+            let accounts = accounts();
+            let supply = 10u128.pow(30);
+            let native = 10u128.pow(24);
+            let mut bank = deploy_funded(supply);
+            bank.transfer(accounts.bob, supply / 4).unwrap();
+            test::set_account_balance::<DefaultEnvironment>(AccountId::from(CONTRACT), native);
+            test::set_account_balance::<DefaultEnvironment>(accounts.bob, 0);
+
+            set_caller(accounts.bob);
+            bank.rage_quit(supply / 4).unwrap();
+            assert_eq!(bank.total_supply(), supply / 4 * 3);
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.bob),
+                Ok(native / 4)
+            );
+        }
+
+        #[ink::test]
+        fn sweep_native_keeps_supply_backing() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let mut bank = deploy_funded(1_000);
+            test::set_account_balance::<DefaultEnvironment>(contract, 3_000_000);
+
+            assert_eq!(
+                bank.sweep_native(accounts.bob, 1),
+                Err(Error::InsufficientReserves)
+            );
+
+            bank.set_reserve_price(2_000).unwrap();
+            assert_eq!(
+                bank.sweep_native(accounts.bob, 1_000_001),
+                Err(Error::InsufficientReserves)
+            );
+            bank.sweep_native(accounts.bob, 1_000_000).unwrap();
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(contract), Ok(2_000_000));
+        }
+
+        #[ink::test]
+        fn min_balance_applies_to_every_send_path() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_min_balance(100).unwrap();
+
+            assert_eq!(
+                bank.can_transfer(accounts.alice, accounts.bob, 950),
+                Err(Error::BelowMinBalance)
+            );
+            assert_eq!(bank.transfer(accounts.bob, 950), Err(Error::BelowMinBalance));
+            assert_eq!(
+                bank.transfer_with_tip(accounts.bob, 900, 50, accounts.charlie),
+                Err(Error::BelowMinBalance)
+            );
+            assert_eq!(
+                bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![900, 50]),
+                Err(Error::BelowMinBalance)
+            );
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 950),
+                Err(Error::BelowMinBalance)
+            );
+
+            bank.approve(accounts.bob, 1_000).unwrap();
+            bank.create_subscription(accounts.bob, 950, 60).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 950),
+                Err(Error::BelowMinBalance)
+            );
+            assert_eq!(bank.pull_subscription(accounts.alice), Err(Error::BelowMinBalance));
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+        }
+
+        #[ink::test]
+        fn min_balance_allows_multi_leg_sends_that_empty_the_sender() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_min_balance(100).unwrap();
+
+            bank.transfer_with_tip(accounts.bob, 500, 50, accounts.charlie).unwrap();
+            bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![400, 50]).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn dust_sweep_sends_leftover_along() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_min_balance(100).unwrap();
+            bank.set_dust_sweep(true).unwrap();
+
+            bank.transfer(accounts.bob, 950).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 0);
+            assert_eq!(bank.balance_of(accounts.bob), 1_000);
+        }
+
+        #[ink::test]
+        fn temp_approval_leaves_no_allowance_behind() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 40).unwrap();
+
+            bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 300).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 300);
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 40);
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 40);
+        }
+
+        #[ink::test]
+        fn temp_approval_honours_approval_and_spender_limits() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.set_pause_flags(PauseFlags::APPROVALS).unwrap();
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 10),
+                Err(Error::ContractPaused)
+            );
+            bank.set_pause_flags(0).unwrap();
+
+            bank.set_allowances_frozen(true).unwrap();
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 10),
+                Err(Error::AllowancesFrozen)
+            );
+            bank.set_allowances_frozen(false).unwrap();
+
+            bank.set_max_allowance(50).unwrap();
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 51),
+                Err(Error::AllowanceTooHigh)
+            );
+        }
+
+        #[ink::test]
+        fn temp_approval_uses_the_callers_transfer_gating() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.charlie, 500).unwrap();
+            bank.set_pause_exempt(accounts.bob, true).unwrap();
+            bank.set_pause_flags(PauseFlags::TRANSFERS).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.bob, accounts.django, 10),
+                Err(Error::ContractPaused)
+            );
+
+            set_caller(accounts.alice);
+            bank.set_pause_flags(0).unwrap();
+            bank.set_spender_daily_limit(50).unwrap();
+            set_caller(accounts.charlie);
+            bank.transfer_with_temp_approval(accounts.bob, accounts.charlie, 50).unwrap();
+            bank.transfer_with_temp_approval(accounts.bob, accounts.django, 50).unwrap();
+            assert_eq!(bank.spender_daily_spent(accounts.bob), 0);
+            assert_eq!(bank.balance_of(accounts.django), 50);
+        }
+
+        #[ink::test]
+        fn large_transfer_queue_approve_and_reject() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_large_transfer_threshold(100).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer(accounts.charlie, 200),
+                Err(Error::LargeTransferRequiresApproval)
+            );
+            assert_eq!(
+                bank.queue_large_transfer(accounts.charlie, 100),
+                Err(Error::NotLargeTransfer)
+            );
+            let approved = bank.queue_large_transfer(accounts.charlie, 200).unwrap();
+            let rejected = bank.queue_large_transfer(accounts.charlie, 150).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+
+            set_caller(accounts.alice);
+            bank.approve_large_transfer(approved).unwrap();
+            bank.reject_large_transfer(rejected).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 200);
+            assert_eq!(bank.pending_large_transfer(rejected), None);
+            assert_eq!(
+                bank.approve_large_transfer(rejected),
+                Err(Error::UnknownLargeTransfer)
+            );
+        }
+
+        #[ink::test]
+        fn large_transfer_runs_transfer_checks_at_queue_and_approval() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 900).unwrap();
+            bank.set_large_transfer_threshold(100).unwrap();
+            bank.toggle_blacklist(accounts.django, true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.queue_large_transfer(accounts.django, 200),
+                Err(Error::AccountBlacklisted)
+            );
+            // Off-chain a failed message doesn't roll back, so each approval
+            // attempt below consumes its queued entry.
+            let ids: Vec<u64> = (0..3)
+                .map(|_| bank.queue_large_transfer(accounts.charlie, 200).unwrap())
+                .collect();
+
+            set_caller(accounts.alice);
+            bank.set_whitelist_mode(true).unwrap();
+            assert_eq!(bank.approve_large_transfer(ids[0]), Err(Error::NotWhitelisted));
+            bank.set_whitelist_mode(false).unwrap();
+
+            bank.set_pause_flags(PauseFlags::TRANSFERS).unwrap();
+            assert_eq!(bank.approve_large_transfer(ids[1]), Err(Error::ContractPaused));
+            bank.set_pause_flags(0).unwrap();
+
+            bank.set_transfers_enabled(false).unwrap();
+            assert_eq!(bank.approve_large_transfer(ids[2]), Err(Error::TransfersDisabled));
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn blacklist_grace_allows_sends_only_within_window() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_blacklist_grace_secs(60).unwrap();
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.alice);
+            bank.approve(accounts.django, 100).unwrap();
+            set_caller(accounts.django);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::AccountBlacklisted)
+            );
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer(accounts.charlie, 100), Err(Error::AccountBlacklisted));
+        }
+
+        #[ink::test]
+        fn blacklist_again_keeps_original_grace_start() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            bank.set_blacklist_grace_secs(60).unwrap();
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(50_000);
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            bank.batch_set_blacklist(vec![accounts.bob], true).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(60_000);
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer(accounts.charlie, 100), Err(Error::AccountBlacklisted));
+        }
+
+        #[ink::test]
+        fn guardian_pauses_only_after_owner_inactivity() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_backup_guardian(Some(accounts.bob)).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(10_000);
+            set_caller(accounts.bob);
+            assert_eq!(bank.emergency_pause(), Err(Error::InactivityNotSet));
+
+            set_caller(accounts.alice);
+            bank.set_inactivity_secs(60).unwrap();
+            set_caller(accounts.bob);
+            test::set_block_timestamp::<DefaultEnvironment>(70_000);
+            assert_eq!(bank.emergency_pause(), Err(Error::OwnerStillActive));
+
+            set_caller(accounts.charlie);
+            test::set_block_timestamp::<DefaultEnvironment>(70_001);
+            assert_eq!(bank.emergency_pause(), Err(Error::NotGuardian));
+
+            set_caller(accounts.bob);
+            bank.emergency_pause().unwrap();
+            assert_eq!(bank.pause_flags(), PauseFlags::ALL);
+        }
+
+        #[ink::test]
+        fn supported_features_reports_recent_transfers() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::RECENT_TRANSFERS, 0);
+            bank.set_record_recent_transfers(true).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::RECENT_TRANSFERS,
+                FeatureFlags::RECENT_TRANSFERS
+            );
+        }
+
+        #[ink::test]
+        fn supported_features_reports_reserve_ratio() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::RESERVE_RATIO, 0);
+            bank.set_min_reserve_ratio_bps(5_000).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::RESERVE_RATIO,
+                FeatureFlags::RESERVE_RATIO
+            );
+        }
+
+        #[ink::test]
+        fn supported_features_reports_compliance_hook() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::COMPLIANCE_HOOK, 0);
+            bank.set_compliance_hook(Some(accounts().django)).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::COMPLIANCE_HOOK,
+                FeatureFlags::COMPLIANCE_HOOK
+            );
+        }
+
+        #[ink::test]
+        fn supported_features_reports_spender_daily_limit() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::SPENDER_DAILY_LIMIT, 0);
+            bank.set_spender_daily_limit(1_000).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::SPENDER_DAILY_LIMIT,
+                FeatureFlags::SPENDER_DAILY_LIMIT
+            );
+        }
+
+        #[ink::test]
+        fn supported_features_reports_hold_period() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::HOLD_PERIOD, 0);
+            bank.set_hold_secs(60).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::HOLD_PERIOD,
+                FeatureFlags::HOLD_PERIOD
+            );
+        }
+
+        #[ink::test]
+        fn supported_features_reports_mint_allowlist() {
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features() & FeatureFlags::MINT_ALLOWLIST, 0);
+            bank.set_mint_allowlist_enabled(true).unwrap();
+            assert_eq!(
+                bank.supported_features() & FeatureFlags::MINT_ALLOWLIST,
+                FeatureFlags::MINT_ALLOWLIST
+            );
+        }
+
+        #[ink::test]
+        fn hold_period_leaves_overspend_to_balance_check() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_hold_secs(60).unwrap();
+            bank.transfer(accounts.bob, 100).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer(accounts.charlie, 100), Err(Error::HoldPeriodActive));
+            assert_eq!(bank.transfer(accounts.charlie, 101), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn batch_blacklist_applies_status_to_every_account() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            let targets = vec![accounts.bob, accounts.charlie, accounts.django];
+
+            bank.batch_set_blacklist(targets.clone(), true).unwrap();
+            assert!(targets.iter().all(|account| bank.is_blacklisted(*account)));
+            assert_eq!(test::recorded_events().count(), 3);
+
+            bank.batch_set_blacklist(targets.clone(), false).unwrap();
+            assert!(targets.iter().all(|account| !bank.is_blacklisted(*account)));
+        }
+
+        #[ink::test]
+        fn batch_blacklist_rejects_more_than_max_batch_size() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_max_batch_size(2).unwrap();
+
+            assert_eq!(
+                bank.batch_set_blacklist(vec![accounts.bob, accounts.charlie, accounts.django], true),
+                Err(Error::InvalidBatchOperation)
+            );
+            assert!(!bank.is_blacklisted(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.batch_set_blacklist(vec![accounts.charlie], true),
+                Err(Error::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        fn pause_exempt_caller_transfers_while_paused() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+            bank.transfer(accounts.charlie, 100).unwrap();
+            bank.set_pause_exempt(accounts.bob, true).unwrap();
+            bank.set_pause_exempt(accounts.eve, true).unwrap();
+            bank.set_pause_flags(PauseFlags::TRANSFERS).unwrap();
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.django, 10).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(bank.transfer(accounts.django, 10), Err(Error::ContractPaused));
+            bank.approve(accounts.eve, 10).unwrap();
+            bank.approve(accounts.frank, 10).unwrap();
+
+            set_caller(accounts.frank);
+            assert_eq!(
+                bank.transfer_from(accounts.charlie, accounts.django, 10),
+                Err(Error::ContractPaused)
+            );
+
+            set_caller(accounts.eve);
+            bank.transfer_from(accounts.charlie, accounts.django, 10).unwrap();
+            assert_eq!(bank.balance_of(accounts.django), 20);
+        }
+
+        #[ink::test]
+        fn compact_batch_emits_one_summary_event() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_compact_batch_events(true).unwrap();
+            let before = test::recorded_events().count();
+
+            bank.batch_transfer(vec![accounts.bob, accounts.charlie, accounts.django], vec![10, 20, 30])
+                .unwrap();
+
+            let events: Vec<_> = test::recorded_events().skip(before).collect();
+            assert_eq!(events.len(), 1);
+            let summary = <BatchTransfer as scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!(summary.from, accounts.alice);
+            assert_eq!(summary.count, 3);
+            assert_eq!(summary.total, 60);
+        }
+
+        #[ink::test]
+        fn verbose_batch_emits_one_event_per_leg() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let before = test::recorded_events().count();
+
+            bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![10, 20]).unwrap();
+            assert_eq!(test::recorded_events().count() - before, 2);
+        }
+
+        #[ink::test]
+        fn contract_balance_reflects_escrowed_stake() {
+            let mut bank = deploy_funded(1_000);
+            assert_eq!(bank.contract_balance(), 0);
+
+            bank.stake(300).unwrap();
+            assert_eq!(bank.contract_balance(), 300);
+            assert_eq!(bank.contract_balance(), bank.balance_of(AccountId::from(CONTRACT)));
+        }
+
+        #[ink::test]
+        fn whitelist_mode_requires_both_parties_listed() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_whitelisted(accounts.alice, true).unwrap();
+            bank.set_whitelisted(accounts.bob, true).unwrap();
+            bank.set_whitelist_mode(true).unwrap();
+
+            bank.transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.transfer(accounts.charlie, 100), Err(Error::NotWhitelisted));
+
+            bank.approve(accounts.charlie, 50).unwrap();
+            set_caller(accounts.charlie);
+            bank.transfer_from(accounts.alice, accounts.bob, 50).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 150);
+
+            set_caller(accounts.alice);
+            bank.set_whitelist_mode(false).unwrap();
+            bank.transfer(accounts.charlie, 100).unwrap();
+            bank.set_whitelist_mode(true).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(bank.transfer(accounts.django, 10), Err(Error::NotWhitelisted));
+            assert_eq!(bank.transfer(accounts.bob, 10), Err(Error::NotWhitelisted));
+        }
+
+        #[ink::test]
+        fn whitelist_mode_gates_mint_recipients() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_whitelisted(accounts.bob, true).unwrap();
+            bank.set_whitelist_mode(true).unwrap();
+
+            bank.mint(accounts.bob, 100).unwrap();
+            assert_eq!(bank.mint(accounts.charlie, 100), Err(Error::NotWhitelisted));
+        }
+
+        #[ink::test]
+        fn safe_transfer_to_plain_account_behaves_like_transfer() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.safe_transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 100);
+            assert_eq!(bank.safe_transfer(accounts.bob, 5_000), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn transfers_disabled_blocks_sends_but_not_mint_or_burn() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 100).unwrap();
+            bank.set_transfers_enabled(false).unwrap();
+
+            assert_eq!(bank.transfer(accounts.bob, 10), Err(Error::TransfersDisabled));
+            assert_eq!(
+                bank.batch_transfer(vec![accounts.bob], vec![10]),
+                Err(Error::TransfersDisabled)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::TransfersDisabled)
+            );
+
+            set_caller(accounts.alice);
+            bank.mint(accounts.bob, 50).unwrap();
+            bank.burn(100).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 50);
+            assert_eq!(bank.total_supply(), 950);
+        }
+
+        #[ink::test]
+        fn self_deposit_block_rejects_direct_sends_but_not_escrow() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let contract = AccountId::from(CONTRACT);
+            bank.set_block_self_deposit(true).unwrap();
+
+            assert_eq!(bank.transfer(contract, 10), Err(Error::SelfDepositBlocked));
+            bank.approve(accounts.bob, 10).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, contract, 10),
+                Err(Error::SelfDepositBlocked)
+            );
+
+            set_caller(accounts.alice);
+            bank.stake(200).unwrap();
+            assert_eq!(bank.contract_balance(), 200);
+            bank.unstake(200).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+        }
+
+        #[ink::test]
+        fn approve_if_current_applies_on_matching_allowance() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.approve(accounts.bob, 100).unwrap();
+
+            bank.approve_if_current(accounts.bob, 100, 40).unwrap();
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn approve_if_current_rejects_stale_expectation() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.approve(accounts.bob, 100).unwrap();
+
+            assert_eq!(
+                bank.approve_if_current(accounts.bob, 90, 40),
+                Err(Error::AllowanceChanged)
+            );
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn config_snapshots_configured_limits() {
+            let mut bank = deploy();
+            bank.set_max_wallet_amount(500).unwrap();
+            bank.set_max_batch_size(7).unwrap();
+            bank.set_transfers_enabled(false).unwrap();
+            bank.set_pause_flags(PauseFlags::MINTS).unwrap();
+            bank.set_hold_secs(30).unwrap();
+
+            let config = bank.config();
+            assert_eq!(config.max_wallet_amount, 500);
+            assert_eq!(config.max_batch_size, 7);
+            assert!(!config.transfers_enabled);
+            assert_eq!(config.pause_flags, PauseFlags::MINTS);
+            assert_eq!(config.hold_secs, 30);
+            assert_eq!(config.max_allowance, bank.max_allowance());
+        }
+
+        #[ink::test]
+        fn self_approval_allowed_by_default() {
+            let accounts = accounts();
+            let mut bank = deploy();
+
+            bank.approve(accounts.alice, 10).unwrap();
+            assert_eq!(bank.allowance(accounts.alice, accounts.alice), 10);
+        }
+
+        #[ink::test]
+        fn self_approval_rejected_when_blocked() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_block_self_approval(true).unwrap();
+
+            assert_eq!(bank.approve(accounts.alice, 10), Err(Error::SelfApproval));
+            assert_eq!(
+                bank.transfer_with_temp_approval(accounts.alice, accounts.bob, 10),
+                Err(Error::SelfApproval)
+            );
+            bank.approve(accounts.bob, 10).unwrap();
+        }
+
+        #[ink::test]
+        fn zeroed_balance_frees_its_storage_entry() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.holder_count(), 2);
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.alice, 100).unwrap();
+            assert!(!bank.balances.contains(accounts.bob));
+            assert_eq!(bank.holder_count(), 1);
+
+            set_caller(accounts.alice);
+            bank.transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.holder_count(), 2);
+        }
+
+        #[ink::test]
+        fn zeroed_balance_kept_when_pruning_off() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_prune_zero_balances(false).unwrap();
+            bank.transfer(accounts.bob, 100).unwrap();
+
+            set_caller(accounts.bob);
+            bank.transfer(accounts.alice, 100).unwrap();
+            assert!(bank.balances.contains(accounts.bob));
+            assert_eq!(bank.holder_count(), 1);
+        }
+
+        #[ink::test]
+        fn event_seq_increments_without_gaps() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let last_event = || test::recorded_events().last().unwrap().data;
+
+            let minted = <Minted as scale::Decode>::decode(&mut &last_event()[..]).unwrap();
+            assert_eq!(minted.seq, 1);
+
+            bank.transfer(accounts.bob, 100).unwrap();
+            let sent = <Transfer as scale::Decode>::decode(&mut &last_event()[..]).unwrap();
+            assert_eq!(sent.seq, 2);
+
+            bank.burn(50).unwrap();
+            let burned = <Burned as scale::Decode>::decode(&mut &last_event()[..]).unwrap();
+            assert_eq!(burned.seq, 3);
+
+            assert_eq!(bank.transfer(accounts.bob, 5_000), Err(Error::InsufficientBalance));
+            bank.mint(accounts.bob, 10).unwrap();
+            let minted = <Minted as scale::Decode>::decode(&mut &last_event()[..]).unwrap();
+            assert_eq!(minted.seq, 4);
+            assert_eq!(bank.event_seq(), 4);
+        }
+
+        #[ink::test]
+        fn max_allowance_accepts_cap_and_rejects_above() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            assert_eq!(bank.max_allowance(), u128::MAX);
+            bank.set_max_allowance(500).unwrap();
+
+            bank.approve(accounts.bob, 500).unwrap();
+            assert_eq!(bank.approve(accounts.bob, 501), Err(Error::AllowanceTooHigh));
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 500);
+        }
+
+        #[ink::test]
+        fn scaled_views_divide_by_unit_scale() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(10_500);
+            bank.transfer(accounts.bob, 2_999).unwrap();
+            assert_eq!(bank.balance_of_scaled(accounts.bob), 2_999);
+
+            bank.set_unit_scale(1_000).unwrap();
+            assert_eq!(bank.balance_of_scaled(accounts.alice), 7);
+            assert_eq!(bank.balance_of_scaled(accounts.bob), 2);
+            assert_eq!(bank.total_supply_scaled(), 10);
+            assert_eq!(bank.set_unit_scale(0), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn frozen_allowances_block_transfer_from_only() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 100).unwrap();
+            bank.set_allowances_frozen(true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::AllowancesFrozen)
+            );
+
+            set_caller(accounts.alice);
+            bank.transfer(accounts.bob, 10).unwrap();
+            bank.burn(10).unwrap();
+
+            bank.set_allowances_frozen(false).unwrap();
+            set_caller(accounts.bob);
+            bank.transfer_from(accounts.alice, accounts.bob, 10).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 20);
+        }
+
+        #[ink::test]
+        fn transfer_errors_instead_of_clamping_recipient() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_balance(accounts.bob, u128::MAX - 10);
+
+            assert_eq!(bank.transfer(accounts.bob, 11), Err(Error::Overflow));
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+            assert_eq!(bank.balance_of(accounts.bob), u128::MAX - 10);
+
+            bank.transfer(accounts.bob, 10).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), u128::MAX);
+        }
+
+        #[ink::test]
+        fn pausing_mints_leaves_transfers_running() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_pause_flags(PauseFlags::MINTS).unwrap();
+
+            assert_eq!(bank.mint(accounts.bob, 10), Err(Error::ContractPaused));
+            bank.transfer(accounts.bob, 10).unwrap();
+            bank.burn(10).unwrap();
+            bank.approve(accounts.bob, 10).unwrap();
+        }
+
+        #[ink::test]
+        fn toggle_pause_sets_and_clears_every_flag() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.toggle_pause(true).unwrap();
+            assert_eq!(bank.pause_flags(), PauseFlags::ALL);
+            assert_eq!(bank.transfer(accounts.bob, 10), Err(Error::ContractPaused));
+            assert_eq!(bank.burn(10), Err(Error::ContractPaused));
+            assert_eq!(bank.approve(accounts.bob, 10), Err(Error::ContractPaused));
+
+            bank.toggle_pause(false).unwrap();
+            assert_eq!(bank.pause_flags(), 0);
+            bank.transfer(accounts.bob, 10).unwrap();
+        }
+
+        #[ink::test]
+        fn invoice_ids_are_recorded_once() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.transfer_with_invoice(accounts.bob, 100, 7).unwrap();
+            assert_eq!(bank.invoice(7), Some((accounts.alice, accounts.bob, 100)));
+
+            assert_eq!(
+                bank.transfer_with_invoice(accounts.charlie, 50, 7),
+                Err(Error::DuplicateInvoice)
+            );
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+            assert_eq!(bank.invoice(7), Some((accounts.alice, accounts.bob, 100)));
+            assert_eq!(bank.invoice(8), None);
+        }
+
+        #[ink::test]
+        fn distribute_from_spends_one_allowance() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 100).unwrap();
+
+            set_caller(accounts.bob);
+            bank.distribute_from(accounts.alice, vec![accounts.charlie, accounts.django], vec![60, 40])
+                .unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 60);
+            assert_eq!(bank.balance_of(accounts.django), 40);
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn distribute_from_rejects_total_above_allowance() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 99).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.distribute_from(accounts.alice, vec![accounts.charlie, accounts.django], vec![60, 40]),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 99);
+        }
+
+        #[ink::test]
+        fn zero_transfers_rejected_by_default() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 10).unwrap();
+
+            assert_eq!(bank.transfer(accounts.bob, 0), Err(Error::ZeroAmount));
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer_from(accounts.alice, accounts.bob, 0), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn zero_transfers_succeed_as_noops_when_allowed() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_allow_zero_transfers(true).unwrap();
+            bank.approve(accounts.bob, 10).unwrap();
+            let before = test::recorded_events().count();
+
+            bank.transfer(accounts.bob, 0).unwrap();
+            set_caller(accounts.bob);
+            bank.transfer_from(accounts.alice, accounts.bob, 0).unwrap();
+
+            let events: Vec<_> = test::recorded_events().skip(before).collect();
+            assert_eq!(events.len(), 2);
+            let sent = <Transfer as scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!((sent.from, sent.to, sent.amount), (accounts.alice, accounts.bob, 0));
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn sweep_native_recovers_stray_funds() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let mut bank = deploy();
+            test::set_account_balance::<DefaultEnvironment>(contract, 5_000_000);
+            test::set_account_balance::<DefaultEnvironment>(accounts.bob, 0);
+
+            bank.sweep_native(accounts.bob, 2_000_000).unwrap();
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.bob), Ok(2_000_000));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(contract), Ok(3_000_000));
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.sweep_native(accounts.bob, 1_000_000), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn mint_cooldown_blocks_until_elapsed() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_mint_cooldown_secs(60).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+
+            bank.mint(accounts.bob, 10).unwrap();
+            assert_eq!(bank.last_mint_at(), Some(1_000));
+            test::set_block_timestamp::<DefaultEnvironment>(60_999);
+            assert_eq!(bank.mint(accounts.bob, 10), Err(Error::MintCooldownActive));
+
+            test::set_block_timestamp::<DefaultEnvironment>(61_000);
+            bank.mint(accounts.bob, 10).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 20);
+        }
+
+        #[ink::test]
+        fn total_allowance_received_sums_every_owner() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+
+            bank.approve(accounts.charlie, 100).unwrap();
+            set_caller(accounts.bob);
+            bank.approve(accounts.charlie, 50).unwrap();
+            assert_eq!(bank.total_allowance_received(accounts.charlie), 150);
+
+            set_caller(accounts.charlie);
+            bank.transfer_from(accounts.alice, accounts.charlie, 30).unwrap();
+            assert_eq!(bank.total_allowance_received(accounts.charlie), 120);
+
+            set_caller(accounts.bob);
+            bank.approve(accounts.charlie, u128::MAX).unwrap();
+            assert_eq!(bank.total_allowance_received(accounts.charlie), u128::MAX);
+            bank.approve(accounts.charlie, 0).unwrap();
+            assert_eq!(bank.total_allowance_received(accounts.charlie), 70);
+        }
+
+        #[ink::test]
+        fn block_mode_refuses_seizure() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+
+            assert_eq!(bank.blacklist_action(), BlacklistAction::Block);
+            assert_eq!(bank.seize(accounts.bob, accounts.charlie), Err(Error::SeizureDisabled));
+            set_caller(accounts.bob);
+            assert_eq!(bank.transfer(accounts.charlie, 10), Err(Error::AccountBlacklisted));
+            assert_eq!(bank.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn seize_mode_moves_blacklisted_balance() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+            bank.set_blacklist_action(BlacklistAction::Seize).unwrap();
+
+            assert_eq!(bank.seize(accounts.bob, accounts.charlie), Err(Error::NotBlacklisted));
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            bank.seize(accounts.bob, accounts.charlie).unwrap();
+
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+            assert_eq!(bank.balance_of(accounts.charlie), 100);
+            let seized =
+                <Transfer as scale::Decode>::decode(&mut &test::recorded_events().last().unwrap().data[..])
+                    .unwrap();
+            assert_eq!((seized.from, seized.to, seized.amount), (accounts.bob, accounts.charlie, 100));
+        }
+
+        #[ink::test]
+        fn batch_hash_is_stable_and_covers_every_element() {
+            let accounts = accounts();
+            let bank = deploy();
+            let recipients = vec![accounts.bob, accounts.charlie];
+            let amounts = vec![10, 20];
+            let hash = bank.batch_hash(recipients.clone(), amounts.clone());
+
+            assert_eq!(bank.batch_hash(recipients.clone(), amounts.clone()), hash);
+            assert_ne!(bank.batch_hash(vec![accounts.bob, accounts.django], amounts.clone()), hash);
+            assert_ne!(bank.batch_hash(recipients.clone(), vec![10, 21]), hash);
+            assert_ne!(bank.batch_hash(vec![accounts.charlie, accounts.bob], amounts), hash);
+            assert_ne!(bank.batch_hash(recipients, vec![20, 10]), hash);
+        }
+
+        #[ink::test]
+        fn transfer_with_tip_pays_both_legs() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let before = test::recorded_events().count();
+
+            bank.transfer_with_tip(accounts.bob, 900, 100, accounts.charlie).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 900);
+            assert_eq!(bank.balance_of(accounts.charlie), 100);
+            assert_eq!(bank.balance_of(accounts.alice), 0);
+            assert_eq!(test::recorded_events().count() - before, 2);
+        }
+
+        #[ink::test]
+        fn transfer_with_tip_rejects_combined_overspend() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            assert_eq!(
+                bank.transfer_with_tip(accounts.bob, 901, 100, accounts.charlie),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                bank.transfer_with_tip(accounts.bob, u128::MAX, 1, accounts.charlie),
+                Err(Error::Overflow)
+            );
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn can_transfer_matches_transfer_outcomes() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+            bank.set_max_wallet_amount(150).unwrap();
+            bank.toggle_blacklist(accounts.django, true).unwrap();
+
+            set_caller(accounts.bob);
+            let cases = [
+                (accounts.charlie, 200),
+                (accounts.django, 10),
+                (accounts.charlie, 0),
+                (accounts.charlie, 10),
+            ];
+            for (to, amount) in cases {
+                let expected = bank.can_transfer(accounts.bob, to, amount);
+                assert_eq!(bank.transfer(to, amount), expected);
+            }
+
+            set_caller(accounts.alice);
+            bank.transfer(accounts.charlie, 140).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.can_transfer(accounts.bob, accounts.charlie, 20),
+                Err(Error::ExceedsMaxWallet)
+            );
+            assert_eq!(bank.transfer(accounts.charlie, 20), Err(Error::ExceedsMaxWallet));
+
+            set_caller(accounts.alice);
+            bank.set_min_balance(30).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.can_transfer(accounts.bob, accounts.eve, 70),
+                Err(Error::BelowMinBalance)
+            );
+            assert_eq!(bank.transfer(accounts.eve, 70), Err(Error::BelowMinBalance));
+
+            set_caller(accounts.alice);
+            bank.set_dust_sweep(true).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.can_transfer(accounts.bob, accounts.eve, 70), Ok(()));
+            bank.transfer(accounts.eve, 70).unwrap();
+            assert_eq!(bank.balance_of(accounts.eve), 90);
+
+            set_caller(accounts.alice);
+            bank.set_pause_flags(PauseFlags::TRANSFERS).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.can_transfer(accounts.bob, accounts.charlie, 1),
+                Err(Error::ContractPaused)
+            );
+            assert_eq!(bank.transfer(accounts.charlie, 1), Err(Error::ContractPaused));
+        }
+
+        #[ink::test]
+        fn staking_escrow_moves_without_transfer_events() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            let before = test::recorded_events().count();
+            bank.stake(300).unwrap();
+            let events: Vec<_> = test::recorded_events().skip(before).collect();
+            assert_eq!(events.len(), 1);
+            let staked = <Staked as scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!((staked.account, staked.amount), (accounts.alice, 300));
+            assert_eq!(bank.contract_balance(), 300);
+
+            let before = test::recorded_events().count();
+            bank.transfer(accounts.bob, 100).unwrap();
+            let events: Vec<_> = test::recorded_events().skip(before).collect();
+            assert_eq!(events.len(), 1);
+            let sent = <Transfer as scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!((sent.from, sent.to, sent.amount), (accounts.alice, accounts.bob, 100));
+        }
+
+        #[ink::test]
+        fn soulbound_blocks_transfers_but_not_mint_or_burn() {
+            let accounts = accounts();
+            test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+            let mut bank = InkyBank::with_soulbound(true);
+            bank.mint(accounts.bob, 100).unwrap();
+            bank.mint(accounts.alice, 100).unwrap();
+            bank.approve(accounts.bob, 10).unwrap();
+
+            assert_eq!(bank.transfer(accounts.bob, 10), Err(Error::Soulbound));
+            assert_eq!(bank.batch_transfer(vec![accounts.bob], vec![10]), Err(Error::Soulbound));
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::Soulbound)
+            );
+
+            bank.burn(40).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 60);
+            assert_eq!(bank.total_supply(), 160);
+        }
+
+        #[ink::test]
+        fn renounced_owner_gets_distinct_error() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            assert!(bank.is_owned());
+            set_caller(accounts.bob);
+            assert_eq!(bank.set_max_batch_size(5), Err(Error::NotOwner));
+
+            set_caller(accounts.alice);
+            bank.renounce_ownership().unwrap();
+            assert!(!bank.is_owned());
+            assert_eq!(bank.set_max_batch_size(5), Err(Error::OwnershipRenounced));
+            assert_eq!(bank.mint(accounts.alice, 1), Err(Error::OwnershipRenounced));
+            set_caller(accounts.bob);
+            assert_eq!(bank.set_max_batch_size(5), Err(Error::OwnershipRenounced));
+        }
+
+        #[ink::test]
+        fn burn_prunes_emptied_balance_only_when_enabled() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.mint(accounts.bob, 100).unwrap();
+            bank.mint(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.bob);
+            bank.burn(100).unwrap();
+            assert_eq!(bank.balances.get(accounts.bob), None);
+
+            set_caller(accounts.alice);
+            bank.set_prune_zero_balances(false).unwrap();
+            set_caller(accounts.charlie);
+            bank.burn(100).unwrap();
+            assert_eq!(bank.balances.get(accounts.charlie), Some(0));
+        }
+
+        #[ink::test]
+        fn sub_ledger_mints_and_transfers_separately() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.mint_sub(7, accounts.bob, 300).unwrap();
+            assert_eq!(bank.balance_of_sub(7, accounts.bob), 300);
+            assert_eq!(bank.total_supply_sub(7), 300);
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+
+            set_caller(accounts.bob);
+            bank.transfer_sub(7, accounts.charlie, 120).unwrap();
+            assert_eq!(bank.balance_of_sub(7, accounts.bob), 180);
+            assert_eq!(bank.balance_of_sub(7, accounts.charlie), 120);
+            assert_eq!(bank.transfer_sub(7, accounts.charlie, 181), Err(Error::InsufficientBalance));
+            assert_eq!(bank.total_supply_sub(7), 300);
+
+            set_caller(accounts.alice);
+            bank.transfer_sub(0, accounts.bob, 50).unwrap();
+            assert_eq!(bank.balance_of_sub(0, accounts.bob), 50);
+            assert_eq!(bank.total_supply_sub(0), bank.total_supply());
+        }
+
+        #[ink::test]
+        fn daily_limit_exemption_leaves_other_limits_in_place() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 1_000).unwrap();
+            bank.approve(accounts.charlie, 1_000).unwrap();
+            bank.set_spender_daily_limit(100).unwrap();
+            bank.set_max_wallet_amount(300).unwrap();
+            bank.set_exemption_flags(accounts.bob, ExemptionFlags::DAILY_LIMIT).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.django, 101),
+                Err(Error::DailyLimitExceeded)
+            );
+
+            set_caller(accounts.bob);
+            bank.transfer_from(accounts.alice, accounts.django, 250).unwrap();
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.django, 51),
+                Err(Error::ExceedsMaxWallet)
+            );
+            assert_eq!(bank.exemption_flags(accounts.bob), ExemptionFlags::DAILY_LIMIT);
+        }
+
+        #[ink::test]
+        fn derive_subaccount_is_deterministic_and_distinct() {
+            let accounts = accounts();
+            let bank = deploy();
+            let first = bank.derive_subaccount(accounts.bob, 0);
+
+            assert_eq!(bank.derive_subaccount(accounts.bob, 0), first);
+            assert_ne!(bank.derive_subaccount(accounts.bob, 1), first);
+            assert_ne!(bank.derive_subaccount(accounts.charlie, 0), first);
+            assert_ne!(first, accounts.bob);
+        }
+
+        #[ink::test]
+        fn bridge_burn_burns_and_emits_numbered_event() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.bridge_burn(300, 9, [7; 32]).unwrap();
+            let burn =
+                <BridgeBurn as scale::Decode>::decode(&mut &test::recorded_events().last().unwrap().data[..])
+                    .unwrap();
+            assert_eq!(burn.from, accounts.alice);
+            assert_eq!(burn.amount, 300);
+            assert_eq!(burn.dest_chain, 9);
+            assert_eq!(burn.dest_address, [7; 32]);
+            assert_eq!(burn.nonce, 0);
+            assert_eq!(bank.total_supply(), 700);
+            assert_eq!(bank.balance_of(accounts.alice), 700);
+
+            bank.bridge_burn(100, 9, [7; 32]).unwrap();
+            assert_eq!(bank.bridge_nonce(), 2);
+            assert_eq!(bank.bridge_burn(701, 9, [7; 32]), Err(Error::InsufficientBalance));
+            assert_eq!(bank.bridge_nonce(), 2);
+        }
+
+        #[ink::test]
+        fn bridge_mint_runs_once_per_source_nonce() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_bridge_relayer(Some(accounts.bob)).unwrap();
+            assert_eq!(bank.bridge_mint(accounts.charlie, 100, 9, 0), Err(Error::NotRelayer));
+
+            set_caller(accounts.bob);
+            bank.bridge_mint(accounts.charlie, 100, 9, 0).unwrap();
+            assert!(bank.is_bridge_mint_processed(9, 0));
+            assert_eq!(bank.balance_of(accounts.charlie), 100);
+            assert_eq!(bank.total_supply(), 100);
+
+            assert_eq!(bank.bridge_mint(accounts.charlie, 100, 9, 0), Err(Error::AlreadyProcessed));
+            bank.bridge_mint(accounts.charlie, 100, 10, 0).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 200);
+        }
+
+        #[ink::test]
+        fn supported_features_sets_bits_for_enabled_capabilities() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            assert_eq!(bank.supported_features(), 0);
+
+            bank.set_bridge_relayer(Some(accounts.bob)).unwrap();
+            bank.set_max_wallet_amount(1_000).unwrap();
+            assert_eq!(
+                bank.supported_features(),
+                FeatureFlags::BRIDGING | FeatureFlags::MAX_WALLET
+            );
+
+            bank.set_bridge_relayer(None).unwrap();
+            assert_eq!(bank.supported_features(), FeatureFlags::MAX_WALLET);
+        }
+
+        #[ink::test]
+        fn recent_transfers_lists_newest_first() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.eve, 1).unwrap();
+            bank.set_record_recent_transfers(true).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(10);
+            bank.transfer(accounts.bob, 10).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(20);
+            bank.transfer(accounts.charlie, 20).unwrap();
+            set_caller(accounts.bob);
+            bank.transfer(accounts.django, 5).unwrap();
+
+            assert_eq!(
+                bank.recent_transfers(),
+                vec![
+                    (accounts.bob, accounts.django, 5, 20),
+                    (accounts.alice, accounts.charlie, 20, 20),
+                    (accounts.alice, accounts.bob, 10, 10),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn recent_transfers_keeps_only_the_last_sixteen() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_record_recent_transfers(true).unwrap();
+
+            for amount in 1..=20 {
+                bank.transfer(accounts.bob, amount).unwrap();
+            }
+
+            let recent = bank.recent_transfers();
+            assert_eq!(recent.len(), 16);
+            assert_eq!(recent[0].2, 20);
+            assert_eq!(recent[15].2, 5);
+        }
+
+        #[ink::test]
+        fn swap_completes_on_exact_payment() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let mut bank = deploy_funded(1_000);
+            let id = bank.create_swap(accounts.bob, 400, 1_000_000, 5_000).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 600);
+            assert_eq!(bank.contract_balance(), 400);
+
+            set_caller(accounts.charlie);
+            assert_eq!(bank.accept_swap(id), Err(Error::NotSwapParty));
+
+            set_caller(accounts.bob);
+            test::set_value_transferred::<DefaultEnvironment>(999_999);
+            assert_eq!(bank.accept_swap(id), Err(Error::IncorrectPayment));
+
+            test::set_value_transferred::<DefaultEnvironment>(1_000_000);
+            test::set_account_balance::<DefaultEnvironment>(contract, 2_000_000);
+            let seller_native =
+                test::get_account_balance::<DefaultEnvironment>(accounts.alice).unwrap();
+            bank.accept_swap(id).unwrap();
+
+            assert_eq!(bank.balance_of(accounts.bob), 400);
+            assert_eq!(bank.contract_balance(), 0);
+            assert_eq!(bank.swap(id), None);
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.alice),
+                Ok(seller_native + 1_000_000)
+            );
+        }
+
+        #[ink::test]
+        fn expired_swap_cancels_back_to_seller() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let id = bank.create_swap(accounts.bob, 400, 1_000_000, 5_000).unwrap();
+            assert_eq!(bank.cancel_swap(id), Err(Error::SwapNotExpired));
+
+            test::set_block_timestamp::<DefaultEnvironment>(5_001);
+            set_caller(accounts.bob);
+            test::set_value_transferred::<DefaultEnvironment>(1_000_000);
+            assert_eq!(bank.accept_swap(id), Err(Error::SwapExpired));
+
+            set_caller(accounts.alice);
+            bank.cancel_swap(id).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+            assert_eq!(bank.total_escrowed(), 0);
+            assert_eq!(bank.cancel_swap(id), Err(Error::UnknownSwap));
+        }
+
+        #[ink::test]
+        fn batch_with_dust_entry_is_rejected_whole() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_min_transfer_amount(10).unwrap();
+
+            assert_eq!(
+                bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![50, 9]),
+                Err(Error::BelowMinimum)
+            );
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+
+            bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![50, 10]).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 50);
+            assert_eq!(bank.balance_of(accounts.charlie), 10);
+        }
+
+        #[ink::test]
+        fn reserve_ratio_caps_mints_by_native_backing() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let mut bank = deploy();
+            test::set_account_balance::<DefaultEnvironment>(contract, 10_000_000);
+            bank.set_reserve_price(1_000).unwrap();
+            bank.set_min_reserve_ratio_bps(5_000).unwrap();
+
+            bank.mint(accounts.bob, 20_000).unwrap();
+            assert_eq!(bank.mint(accounts.bob, 1), Err(Error::InsufficientReserves));
+
+            bank.set_min_reserve_ratio_bps(0).unwrap();
+            bank.mint(accounts.bob, 1).unwrap();
+        }
+
+        #[ink::test]
+        fn burn_all_empties_and_prunes_the_balance() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 400).unwrap();
+
+            bank.burn_all().unwrap();
+            assert_eq!(bank.total_supply(), 400);
+            assert!(!bank.balances.contains(accounts.alice));
+            let burned =
+                <Burned as scale::Decode>::decode(&mut &test::recorded_events().last().unwrap().data[..])
+                    .unwrap();
+            assert_eq!((burned.from, burned.amount), (accounts.alice, 600));
+
+            assert_eq!(bank.burn_all(), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn blacklist_without_hook_makes_no_call() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            assert_eq!(bank.compliance_hook(), None);
+
+            // The off-chain engine can't run cross-contract calls, so only the
+            // unhooked path is exercised here.
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            assert!(bank.is_blacklisted(accounts.bob));
+            assert_eq!(test::recorded_events().count(), 1);
+
+            bank.set_compliance_hook(Some(accounts.django)).unwrap();
+            assert_eq!(bank.compliance_hook(), Some(accounts.django));
+            bank.set_compliance_hook(None).unwrap();
+            bank.toggle_blacklist(accounts.bob, false).unwrap();
+            assert!(!bank.is_blacklisted(accounts.bob));
+        }
+
+        #[ink::test]
+        fn batch_failing_at_second_entry_leaves_first_untouched() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.toggle_blacklist(accounts.charlie, true).unwrap();
+
+            assert_eq!(
+                bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![100, 100]),
+                Err(Error::AccountBlacklisted)
+            );
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+            let recipients = vec![accounts.bob, accounts.charlie];
+            assert_eq!(
+                bank.validate_batch_transfer(accounts.alice, recipients, vec![100, 100]),
+                Err((1, Error::AccountBlacklisted))
+            );
+        }
+
+        #[ink::test]
+        fn mint_allowlist_gates_bridge_mints() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_bridge_relayer(Some(accounts.bob)).unwrap();
+            bank.set_mint_allowlisted(accounts.charlie, true).unwrap();
+            bank.set_mint_allowlist_enabled(true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.bridge_mint(accounts.django, 100, 9, 0), Err(Error::NotWhitelisted));
+            assert!(!bank.is_bridge_mint_processed(9, 0));
+            bank.bridge_mint(accounts.charlie, 100, 9, 0).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn created_at_records_deployment_block_and_time() {
+            test::advance_block::<DefaultEnvironment>();
+            test::advance_block::<DefaultEnvironment>();
+            test::set_block_timestamp::<DefaultEnvironment>(42_000);
+            let block = ink::env::block_number::<DefaultEnvironment>();
+            let bank = deploy();
+
+            test::advance_block::<DefaultEnvironment>();
+            test::set_block_timestamp::<DefaultEnvironment>(99_000);
+            assert_eq!(bank.created_at(), (block, 42_000));
+        }
+
+        #[ink::test]
+        fn spender_daily_limit_resets_after_a_day() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, 1_000).unwrap();
+            bank.set_spender_daily_limit(100).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+
+            set_caller(accounts.bob);
+            bank.transfer_from(accounts.alice, accounts.charlie, 60).unwrap();
+            bank.transfer_from(accounts.alice, accounts.charlie, 40).unwrap();
+            assert_eq!(bank.spender_daily_spent(accounts.bob), 100);
+            assert_eq!(
+                bank.transfer_from(accounts.alice, accounts.charlie, 1),
+                Err(Error::DailyLimitExceeded)
+            );
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_000 + DAY_MS);
+            bank.transfer_from(accounts.alice, accounts.charlie, 100).unwrap();
+            assert_eq!(bank.balance_of(accounts.charlie), 200);
+        }
+
+        #[ink::test]
+        fn total_escrowed_tracks_stakes_and_swaps() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+
+            bank.stake(300).unwrap();
+            let id = bank.create_swap(accounts.bob, 200, 0, 5_000).unwrap();
+            assert_eq!(bank.total_escrowed(), 500);
+
+            bank.unstake(100).unwrap();
+            assert_eq!(bank.total_escrowed(), 400);
+
+            set_caller(accounts.bob);
+            bank.accept_swap(id).unwrap();
+            assert_eq!(bank.total_escrowed(), 200);
+            assert_eq!(bank.total_escrowed(), bank.contract_balance());
+        }
+
+        #[ink::test]
+        fn rescue_self_sent_only_reaches_the_surplus() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.stake(300).unwrap();
+            bank.transfer(AccountId::from(CONTRACT), 50).unwrap();
+            assert_eq!(bank.contract_balance(), 350);
+
+            assert_eq!(
+                bank.rescue_self_sent(accounts.bob, 51),
+                Err(Error::InsufficientBalance)
+            );
+            bank.rescue_self_sent(accounts.bob, 50).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 50);
+            assert_eq!(bank.contract_balance(), bank.total_escrowed());
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.rescue_self_sent(accounts.bob, 1), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn unhooked_blacklist_changes_report_no_hook_failure() {
+            let accounts = accounts();
+            let mut bank = deploy();
+
+            // A reverting mock hook needs a cross-contract environment; off-chain,
+            // check that the unhooked paths emit only `Blacklisted`.
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+            bank.batch_set_blacklist(vec![accounts.charlie, accounts.django], true).unwrap();
+
+            let events: Vec<_> = test::recorded_events().collect();
+            assert_eq!(events.len(), 3);
+            for event in events {
+                let blacklisted =
+                    <Blacklisted as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+                assert!(blacklisted.status);
+            }
+        }
+
+        #[ink::test]
+        fn group_balance_sums_members() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 200).unwrap();
+            bank.transfer(accounts.charlie, 300).unwrap();
+
+            assert_eq!(
+                bank.group_balance(vec![accounts.alice, accounts.bob, accounts.charlie]),
+                Ok(1_000)
+            );
+            assert_eq!(bank.group_balance(vec![accounts.bob, accounts.django]), Ok(200));
+        }
+
+        #[ink::test]
+        fn group_balance_reports_overflow() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_balance(accounts.bob, u128::MAX);
+            bank.set_balance(accounts.charlie, 1);
+
+            assert_eq!(
+                bank.group_balance(vec![accounts.bob, accounts.charlie]),
+                Err(Error::Overflow)
+            );
+            assert_eq!(bank.group_balance(vec![accounts.bob, accounts.bob]), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn batch_with_blacklisted_third_recipient_moves_nothing() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.toggle_blacklist(accounts.django, true).unwrap();
+            let recipients = vec![accounts.bob, accounts.charlie, accounts.django];
+
+            assert_eq!(
+                bank.validate_batch_transfer(accounts.alice, recipients.clone(), vec![10, 20, 30]),
+                Err((2, Error::AccountBlacklisted))
+            );
+            assert_eq!(
+                bank.batch_transfer(recipients, vec![10, 20, 30]),
+                Err(Error::AccountBlacklisted)
+            );
+            assert_eq!(bank.balance_of(accounts.alice), 1_000);
+            assert_eq!(bank.balance_of(accounts.bob), 0);
+            assert_eq!(bank.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn batch_prevalidation_reports_zero_entries_by_index() {
+            let accounts = accounts();
+            let bank = deploy_funded(1_000);
+
+            assert_eq!(
+                bank.validate_batch_transfer(
+                    accounts.alice,
+                    vec![accounts.bob, accounts.charlie],
+                    vec![10, 0]
+                ),
+                Err((1, Error::ZeroAmount))
+            );
+        }
+
+        #[ink::test]
+        fn mint_allowlist_admits_only_listed_recipients() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            bank.set_mint_allowlisted(accounts.bob, true).unwrap();
+            bank.mint(accounts.charlie, 10).unwrap();
+            bank.set_mint_allowlist_enabled(true).unwrap();
+
+            bank.mint(accounts.bob, 100).unwrap();
+            assert_eq!(bank.mint(accounts.charlie, 100), Err(Error::NotWhitelisted));
+            assert_eq!(bank.balance_of(accounts.charlie), 10);
+
+            bank.set_mint_allowlisted(accounts.bob, false).unwrap();
+            assert_eq!(bank.mint(accounts.bob, 1), Err(Error::NotWhitelisted));
+        }
+
+        #[ink::test]
+        fn storage_cells_count_balance_only_account() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            assert_eq!(bank.account_storage_cells(accounts.bob), 0);
+
+            bank.transfer(accounts.bob, 100).unwrap();
+            assert_eq!(bank.account_storage_cells(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn storage_cells_count_stake_and_allowance_totals() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 100).unwrap();
+
+            set_caller(accounts.bob);
+            bank.stake(40).unwrap();
+            assert_eq!(bank.account_storage_cells(accounts.bob), 3);
+
+            bank.approve(accounts.charlie, 10).unwrap();
+            assert_eq!(bank.account_storage_cells(accounts.bob), 4);
+            assert_eq!(bank.account_storage_cells(accounts.charlie), 1);
+        }
+
+        #[ink::test]
+        fn protected_accounts_cannot_be_blacklisted() {
+            let accounts = accounts();
+            let mut bank = deploy();
+            let contract = AccountId::from(CONTRACT);
+
+            for account in [accounts.alice, contract] {
+                assert_eq!(
+                    bank.toggle_blacklist(account, true),
+                    Err(Error::CannotBlacklistProtected)
+                );
+                assert_eq!(
+                    bank.batch_set_blacklist(vec![account], true),
+                    Err(Error::CannotBlacklistProtected)
+                );
+                assert!(!bank.is_blacklisted(account));
+            }
+            bank.toggle_blacklist(accounts.alice, false).unwrap();
+        }
+
+        #[ink::test]
+        fn migrate_runs_transfer_checks_on_staked_position() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            set_caller(accounts.bob);
+            bank.stake(500).unwrap();
+
+            set_caller(accounts.alice);
+            bank.set_transfers_enabled(false).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.migrate_account(accounts.charlie), Err(Error::TransfersDisabled));
+        }
+
+        #[ink::test]
+        fn migrate_checks_stake_against_recipient_limits() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 500).unwrap();
+            set_caller(accounts.bob);
+            bank.stake(400).unwrap();
+
+            set_caller(accounts.alice);
+            bank.set_max_wallet_amount(450).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.migrate_account(accounts.charlie), Err(Error::ExceedsMaxWallet));
+        }
+
+        #[ink::test]
+        fn hold_period_dust_sends_cannot_extend_a_receipt() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.eve, 10).unwrap();
+            bank.set_hold_secs(60).unwrap();
+            bank.transfer(accounts.bob, 100).unwrap();
+
+            for step in 1..=5u64 {
+                test::set_block_timestamp::<DefaultEnvironment>(step * 50_000);
+                set_caller(accounts.eve);
+                bank.transfer(accounts.bob, 1).unwrap();
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.locked_amount(accounts.bob), 2);
+            bank.transfer(accounts.charlie, 100).unwrap();
+        }
+
+        #[ink::test]
+        fn hold_period_caps_open_locks_per_account() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.eve, 100).unwrap();
+            bank.set_hold_secs(60).unwrap();
+
+            set_caller(accounts.eve);
+            for step in 0..20u64 {
+                test::set_block_timestamp::<DefaultEnvironment>(step * 1_000);
+                bank.transfer(accounts.bob, 1).unwrap();
+            }
+            assert_eq!(bank.hold_tranches.get(accounts.bob).unwrap().len(), MAX_HOLD_TRANCHES);
+            assert_eq!(bank.locked_amount(accounts.bob), 20);
+
+            test::set_block_timestamp::<DefaultEnvironment>(67_000);
+            assert_eq!(bank.locked_amount(accounts.bob), 13);
+            test::set_block_timestamp::<DefaultEnvironment>(79_000);
+            assert_eq!(bank.locked_amount(accounts.bob), 0);
+            assert_eq!(bank.locked_until(accounts.bob), 79_000);
+        }
+
+        #[ink::test]
+        fn distribute_rewards_handles_18_decimal_amounts() {
+            let accounts = accounts();
+            let unit = 10u128.pow(18);
+            let mut bank = deploy_funded(1_000 * unit);
+            bank.transfer(accounts.bob, 250 * unit).unwrap();
+
+            bank.distribute_rewards(vec![accounts.alice, accounts.bob], 1_000 * unit).unwrap();
+            assert_eq!(bank.balance_of(accounts.alice), 1_500 * unit);
+            assert_eq!(bank.balance_of(accounts.bob), 500 * unit);
+            assert_eq!(bank.total_supply(), 2_000 * unit);
+        }
+
+        #[ink::test]
+        fn accept_swap_rechecks_transfer_rules() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            let id = bank.create_swap(accounts.bob, 400, 0, 5_000).unwrap();
+
+            bank.toggle_pause(true).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.accept_swap(id), Err(Error::ContractPaused));
+
+            set_caller(accounts.alice);
+            bank.toggle_pause(false).unwrap();
+            bank.set_transfers_enabled(false).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.accept_swap(id), Err(Error::TransfersDisabled));
+
+            set_caller(accounts.alice);
+            bank.set_transfers_enabled(true).unwrap();
+            bank.set_whitelisted(accounts.alice, true).unwrap();
+            bank.set_whitelist_mode(true).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(bank.accept_swap(id), Err(Error::NotWhitelisted));
+
+            set_caller(accounts.alice);
+            bank.set_whitelisted(accounts.bob, true).unwrap();
+            set_caller(accounts.bob);
+            bank.accept_swap(id).unwrap();
+            assert_eq!(bank.balance_of(accounts.bob), 400);
+        }
+
+        #[ink::test]
+        fn reserve_ratio_covers_bridge_mints_and_rewards() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let mut bank = deploy_funded(1_000);
+            bank.stake(1_000).unwrap();
+            bank.set_reward_per_block(500).unwrap();
+            bank.set_bridge_relayer(Some(accounts.bob)).unwrap();
+            test::set_account_balance::<DefaultEnvironment>(contract, 1_200_000);
+            bank.set_reserve_price(1_000).unwrap();
+            bank.set_min_reserve_ratio_bps(10_000).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                bank.bridge_mint(accounts.charlie, 1_000_000, 9, 0),
+                Err(Error::InsufficientReserves)
+            );
+            bank.bridge_mint(accounts.charlie, 100, 9, 0).unwrap();
+
+            set_caller(accounts.alice);
+            test::advance_block::<DefaultEnvironment>();
+            bank.claim_rewards().unwrap();
+            assert_eq!(bank.total_supply(), 1_200);
+            assert_eq!(bank.pending_reward(accounts.alice), 400);
+        }
+
+        #[ink::test]
+        fn reserve_ratio_handles_large_supplies() {
+            let accounts = accounts();
+            let contract = AccountId::from(CONTRACT);
+            let supply = 10u128.pow(30);
+            let mut bank = deploy_funded(supply);
+            test::set_account_balance::<DefaultEnvironment>(contract, 2 * 10u128.pow(36));
+            bank.set_reserve_price(1_000_000).unwrap();
+            bank.set_min_reserve_ratio_bps(20_000).unwrap();
+
+            assert_eq!(bank.mint(accounts.bob, 1), Err(Error::InsufficientReserves));
+            bank.set_min_reserve_ratio_bps(10_000).unwrap();
+            bank.mint(accounts.bob, supply).unwrap();
+            assert_eq!(bank.mint(accounts.bob, 1), Err(Error::InsufficientReserves));
+        }
+
+        #[ink::test]
+        fn infinite_allowance_is_not_spent_down() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.approve(accounts.bob, u128::MAX).unwrap();
+
+            set_caller(accounts.bob);
+            bank.transfer_from(accounts.alice, accounts.django, 1).unwrap();
+            bank.distribute_from(accounts.alice, vec![accounts.eve], vec![2]).unwrap();
+            assert_eq!(bank.allowance(accounts.alice, accounts.bob), u128::MAX);
+
+            set_caller(accounts.alice);
+            bank.approve(accounts.charlie, 10).unwrap();
+            bank.approve(accounts.charlie, 0).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), u128::MAX);
+            assert_eq!(bank.total_allowance_received(accounts.bob), u128::MAX);
+
+            bank.approve(accounts.bob, 0).unwrap();
+            assert_eq!(bank.total_allowance_granted(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn blacklisted_accounts_cannot_exit_through_rage_quit_or_bridge() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 400).unwrap();
+            test::set_account_balance::<DefaultEnvironment>(AccountId::from(CONTRACT), 10_000_000);
+            bank.toggle_blacklist(accounts.bob, true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(bank.rage_quit(100), Err(Error::AccountBlacklisted));
+            assert_eq!(bank.bridge_burn(100, 9, [7; 32]), Err(Error::AccountBlacklisted));
+            assert_eq!(bank.balance_of(accounts.bob), 400);
+            assert_eq!(bank.total_supply(), 1_000);
+            assert_eq!(bank.bridge_nonce(), 0);
+        }
+
+        #[ink::test]
+        fn staggered_stakers_earn_proportional_rewards() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.transfer(accounts.bob, 300).unwrap();
+            bank.transfer(accounts.charlie, 300).unwrap();
+            bank.set_reward_per_block(30).unwrap();
+
+            set_caller(accounts.bob);
+            bank.stake(100).unwrap();
+            test::advance_block::<DefaultEnvironment>();
+            test::advance_block::<DefaultEnvironment>();
+
+            set_caller(accounts.charlie);
+            bank.stake(200).unwrap();
+            test::advance_block::<DefaultEnvironment>();
+
+            // Bob had the pool alone for two blocks, then a third of it for one.
+            assert_eq!(bank.pending_reward(accounts.bob), 70);
+            assert_eq!(bank.pending_reward(accounts.charlie), 20);
+
+            for account in [accounts.bob, accounts.charlie] {
+                let pending = bank.pending_reward(account);
+                let before = bank.balance_of(account);
+                set_caller(account);
+                bank.claim_rewards().unwrap();
+                assert_eq!(bank.balance_of(account) - before, pending);
+                assert_eq!(bank.pending_reward(account), 0);
+            }
+            assert_eq!(bank.total_supply(), 1_090);
+        }
+
+        #[ink::test]
+        fn recent_transfers_include_compact_batch_legs() {
+            let accounts = accounts();
+            let mut bank = deploy_funded(1_000);
+            bank.set_record_recent_transfers(true).unwrap();
+            bank.set_compact_batch_events(true).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(10);
+
+            bank.batch_transfer(vec![accounts.bob, accounts.charlie], vec![10, 20]).unwrap();
+            assert_eq!(
+                bank.recent_transfers(),
+                vec![
+                    (accounts.alice, accounts.charlie, 20, 10),
+                    (accounts.alice, accounts.bob, 10, 10),
+                ]
+            );
+        }
     }
 }
\ No newline at end of file